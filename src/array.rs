@@ -4,9 +4,15 @@
 #[cfg(not(feature = "use_os"))]
 use alloc::alloc::Layout;
 
-use super::{Error, SecureVec, alloc};
-use core::{marker::PhantomData, mem, ptr::NonNull};
-use zeroize::Zeroize;
+use super::vec::{InitGuard, resolve_range_indices, wipe_slots};
+use super::{Error, SecureSlice, SecureSliceMut, SecureVec, UnlockCount, alloc};
+use core::{
+   marker::PhantomData,
+   mem::{self, MaybeUninit},
+   ops::RangeBounds,
+   ptr::NonNull,
+};
+use zeroize::{DefaultIsZeroes, Zeroize};
 
 #[cfg(feature = "use_os")]
 use super::free;
@@ -21,7 +27,7 @@ struct UnlockGuard<'a, T: Zeroize, const LENGTH: usize> {
 
 impl<'a, T: Zeroize, const LENGTH: usize> UnlockGuard<'a, T, LENGTH> {
    fn new(array: &'a SecureArray<T, LENGTH>) -> Self {
-      let ok = array.unlock_memory();
+      let ok = array.unlocks.acquire(|| array.unlock_memory());
       debug_assert!(ok, "UnlockGuard::new: unlock_memory failed");
       UnlockGuard { array }
    }
@@ -29,7 +35,7 @@ impl<'a, T: Zeroize, const LENGTH: usize> UnlockGuard<'a, T, LENGTH> {
 
 impl<'a, T: Zeroize, const LENGTH: usize> Drop for UnlockGuard<'a, T, LENGTH> {
    fn drop(&mut self) {
-      let ok = self.array.lock_memory();
+      let ok = self.array.unlocks.release(|| self.array.lock_memory());
       debug_assert!(ok, "UnlockGuard::drop: lock_memory failed");
    }
 }
@@ -83,6 +89,7 @@ where
    T: Zeroize,
 {
    ptr: NonNull<T>,
+   unlocks: UnlockCount,
    _marker: PhantomData<T>,
 }
 
@@ -93,10 +100,10 @@ impl<T, const LENGTH: usize> SecureArray<T, LENGTH>
 where
    T: Zeroize,
 {
-   /// Creates an empty (but allocated) SecureArray.
+   /// Allocates the locked buffer without initializing it.
    ///
-   /// The memory is allocated but not initialized, and it's the caller's responsibility to fill it.
-   pub fn empty() -> Result<Self, Error> {
+   /// Only sound for element types that are valid for any bit pattern, eg. `MaybeUninit<T>`.
+   fn allocate() -> Result<Self, Error> {
       let size = LENGTH * mem::size_of::<T>();
       if size == 0 {
          // Cannot create a zero-sized secure array
@@ -107,6 +114,7 @@ where
 
       let secure_array = SecureArray {
          ptr,
+         unlocks: UnlockCount::default(),
          _marker: PhantomData,
       };
 
@@ -120,6 +128,58 @@ where
       Ok(secure_array)
    }
 
+   /// Creates an empty (but allocated) SecureArray.
+   ///
+   /// The memory is allocated but not initialized, and it's the caller's responsibility to fill it.
+   #[deprecated(
+      since = "0.4.0",
+      note = "This exposes uninitialized memory as `T`. Use empty_uninit() or zeroed() instead."
+   )]
+   pub fn empty() -> Result<Self, Error> {
+      Self::allocate()
+   }
+
+   /// Creates an allocated but uninitialized SecureArray.
+   ///
+   /// Fill it through [`unlock_mut`](SecureArray::unlock_mut) and then call
+   /// [`assume_init`](SecureArray::assume_init).
+   ///
+   /// If it is dropped before that, the memory is zeroized but the elements written so far are not dropped.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::SecureArray;
+   ///
+   /// let mut uninit = SecureArray::<u16, 3>::empty_uninit().unwrap();
+   /// uninit.unlock_mut(|slots| {
+   ///     for (i, slot) in slots.iter_mut().enumerate() {
+   ///         slot.write(i as u16);
+   ///     }
+   /// });
+   ///
+   /// // SAFETY: every element was written above
+   /// let array = unsafe { uninit.assume_init() };
+   /// array.unlock(|slice| assert_eq!(slice, &[0, 1, 2]));
+   /// ```
+   pub fn empty_uninit() -> Result<SecureArray<MaybeUninit<T>, LENGTH>, Error> {
+      SecureArray::allocate()
+   }
+
+   /// Creates a SecureArray with every element set to zero.
+   pub fn zeroed() -> Result<Self, Error>
+   where
+      T: DefaultIsZeroes,
+   {
+      let mut uninit = Self::empty_uninit()?;
+      uninit.unlock_mut(|slots| {
+         for slot in slots.iter_mut() {
+            slot.write(T::default());
+         }
+      });
+      Ok(unsafe { uninit.assume_init() })
+   }
+
    /// Creates a new SecureArray from a `&mut [T; LENGTH]`.
    ///
    /// The passed slice is zeroized afterwards
@@ -127,37 +187,17 @@ where
    where
       T: Clone,
    {
-      let secure_array = match Self::empty() {
-         Ok(secure_array) => secure_array,
+      let uninit = match Self::empty_uninit() {
+         Ok(uninit) => uninit,
          Err(e) => {
             content.zeroize();
             return Err(e);
          }
       };
 
-      let unlocked = secure_array.unlock_memory();
-
-      if !unlocked {
-         content.zeroize();
-         return Err(Error::UnlockFailed);
-      }
-
-      unsafe {
-         let dst = secure_array.ptr.as_ptr();
-         for (i, item) in content.iter().enumerate() {
-            core::ptr::write(dst.add(i), item.clone());
-         }
-      }
-
+      let secure_array = uninit.init_from_clone(content);
       content.zeroize();
 
-      let _locked = secure_array.lock_memory();
-
-      #[cfg(feature = "use_os")]
-      if !_locked {
-         return Err(Error::LockFailed);
-      }
-
       Ok(secure_array)
    }
 
@@ -168,29 +208,8 @@ where
    where
       T: Clone,
    {
-      let secure_array = Self::empty()?;
-
-      let unlocked = secure_array.unlock_memory();
-
-      if !unlocked {
-         return Err(Error::UnlockFailed);
-      }
-
-      unsafe {
-         let dst = secure_array.ptr.as_ptr();
-         for (i, item) in content.iter().enumerate() {
-            core::ptr::write(dst.add(i), item.clone());
-         }
-      }
-
-      let _locked = secure_array.lock_memory();
-
-      #[cfg(feature = "use_os")]
-      if !_locked {
-         return Err(Error::LockFailed);
-      }
-
-      Ok(secure_array)
+      let uninit = Self::empty_uninit()?;
+      Ok(uninit.init_from_clone(content))
    }
 
    pub fn len(&self) -> usize {
@@ -217,6 +236,26 @@ where
       self.ptr
    }
 
+   /// Takes ownership of an existing secure allocation.
+   ///
+   /// # Safety
+   /// `ptr` must come from `alloc::<T>(LENGTH * size_of::<T>())`, be locked,
+   /// and hold `LENGTH` initialized elements.
+   pub(crate) unsafe fn from_raw(ptr: NonNull<T>) -> Self {
+      SecureArray {
+         ptr,
+         unlocks: UnlockCount::default(),
+         _marker: PhantomData,
+      }
+   }
+
+   /// Releases ownership of the allocation without zeroizing or freeing it.
+   pub(crate) fn into_raw(self) -> NonNull<T> {
+      let mut array = mem::ManuallyDrop::new(self);
+      unsafe { core::ptr::drop_in_place(&mut array.unlocks) };
+      array.ptr
+   }
+
    pub(crate) fn lock_memory(&self) -> bool {
       #[cfg(feature = "use_os")]
       {
@@ -276,6 +315,34 @@ where
       result
    }
 
+   /// Returns a read-only view of the elements in `range` without copying them
+   ///
+   /// The view shares this array's lock state, see [SecureSlice].
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds.
+   pub fn slice<R>(&self, range: R) -> SecureSlice<'_, T>
+   where
+      R: RangeBounds<usize>,
+   {
+      let (start, end) = resolve_range_indices(range, LENGTH);
+      unsafe { SecureSlice::new(self.ptr, &self.unlocks, start, end - start) }
+   }
+
+   /// Returns a mutable view of the elements in `range` without copying them
+   ///
+   /// The view shares this array's lock state, see [SecureSliceMut].
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds.
+   pub fn slice_mut<R>(&mut self, range: R) -> SecureSliceMut<'_, T>
+   where
+      R: RangeBounds<usize>,
+   {
+      let (start, end) = resolve_range_indices(range, LENGTH);
+      unsafe { SecureSliceMut::new(self.ptr, &self.unlocks, start, end - start) }
+   }
+
    /// Securely erases the contents of the array by zeroizing the memory.
    pub fn erase(&mut self) {
       self.unlock_mut(|slice| {
@@ -285,31 +352,283 @@ where
       });
    }
 
-   /// Same as `SecureVec::init_from_clone`, for the fixed-size buffer.
-   /// `src.len()` must equal `LENGTH`.
-   pub(crate) fn init_from_clone(&mut self, src: &[T])
+   /// Moves the elements in `range` to the end of `dst`, leaving `T::default()` in their place.
+   ///
+   /// The elements are copied directly between the two unlocked secure buffers and
+   /// the source slots are wiped before the default value is written back.
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds or if `dst` fails to grow.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::{SecureArray, SecureVec};
+   ///
+   /// let mut array: SecureArray<u8, 4> = SecureArray::from_slice(&[1, 2, 3, 4]).unwrap();
+   /// let mut vec = SecureVec::new().unwrap();
+   /// array.drain_into(2.., &mut vec);
+   ///
+   /// array.unlock(|slice| assert_eq!(slice, &[1, 2, 0, 0]));
+   /// vec.unlock_slice(|slice| assert_eq!(slice, &[3, 4]));
+   /// ```
+   pub fn drain_into<R>(&mut self, range: R, dst: &mut SecureVec<T>)
    where
-      T: Clone,
+      R: RangeBounds<usize>,
+      T: Default,
    {
-      debug_assert_eq!(src.len(), LENGTH);
+      let (start, end) = resolve_range_indices(range, LENGTH);
+      let count = end - start;
+      if count == 0 {
+         return;
+      }
 
-      let ok = self.unlock_memory();
-      debug_assert!(
-         ok,
-         "SecureArray::init_from_clone: unlock_memory failed"
-      );
+      dst.reserve(count);
 
+      let _guard = UnlockGuard::new(self);
       unsafe {
-         let dst = self.ptr.as_ptr();
-         for (i, item) in src.iter().enumerate() {
-            core::ptr::write(dst.add(i), item.clone());
+         let src = self.ptr.as_ptr().add(start);
+         dst.append_moved(src, count);
+         wipe_slots(src, count);
+         for i in 0..count {
+            core::ptr::write(src.add(i), T::default());
          }
       }
-      let ok = self.lock_memory();
-      debug_assert!(
-         ok,
-         "SecureArray::init_from_clone: lock_memory failed"
-      );
+   }
+
+   /// Clones the elements in `range` to the end of `dst`.
+   ///
+   /// The clones are written directly into `dst`'s unlocked memory, this array is left unchanged.
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds or if `dst` fails to grow.
+   pub fn copy_range_into<R>(&self, range: R, dst: &mut SecureVec<T>)
+   where
+      R: RangeBounds<usize>,
+      T: Clone,
+   {
+      let (start, end) = resolve_range_indices(range, LENGTH);
+      if start == end {
+         return;
+      }
+
+      dst.reserve(end - start);
+      self.unlock(|slice| dst.append_clones(&slice[start..end]));
+   }
+
+   /// Creates a new array by applying `f` to every element.
+   ///
+   /// The results are written straight into the new array's unlocked memory,
+   /// this array is left unchanged.
+   /// If `f` panics, the results it already returned are zeroized and dropped.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::SecureArray;
+   ///
+   /// let key: SecureArray<u8, 4> = SecureArray::from_slice(&[1, 2, 3, 4]).unwrap();
+   /// let ipad = key.map(|byte| byte ^ 0x36).unwrap();
+   /// ipad.unlock(|slice| assert_eq!(slice, &[0x37, 0x34, 0x35, 0x32]));
+   /// ```
+   pub fn map<U, F>(&self, mut f: F) -> Result<SecureArray<U, LENGTH>, Error>
+   where
+      U: Zeroize,
+      F: FnMut(&T) -> U,
+   {
+      let mut out = SecureArray::<U, LENGTH>::empty_uninit()?;
+      self.unlock(|src| {
+         out.unlock_mut(|dst| {
+            let mut init = InitGuard::new(dst);
+            for item in src {
+               init.push(f(item));
+            }
+            init.finish();
+         })
+      });
+      Ok(unsafe { out.assume_init() })
+   }
+
+   /// Creates a new array by applying `f` to every pair of elements of `self` and `other`.
+   ///
+   /// Both inputs are left unchanged.
+   pub fn zip_with<U, V, F>(
+      &self,
+      other: &SecureArray<U, LENGTH>,
+      mut f: F,
+   ) -> Result<SecureArray<V, LENGTH>, Error>
+   where
+      U: Zeroize,
+      V: Zeroize,
+      F: FnMut(&T, &U) -> V,
+   {
+      let mut out = SecureArray::<V, LENGTH>::empty_uninit()?;
+      self.unlock(|a| {
+         other.unlock(|b| {
+            out.unlock_mut(|dst| {
+               let mut init = InitGuard::new(dst);
+               for (x, y) in a.iter().zip(b) {
+                  init.push(f(x, y));
+               }
+               init.finish();
+            })
+         })
+      });
+      Ok(unsafe { out.assume_init() })
+   }
+
+   /// Splits the array into two arrays of `A` and `B` elements.
+   ///
+   /// `A + B` must equal `LENGTH`, otherwise the call fails to compile.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::SecureArray;
+   ///
+   /// let master: SecureArray<u8, 4> = SecureArray::from_slice(&[1, 2, 3, 4]).unwrap();
+   /// let (enc_key, mac_key) = master.split::<1, 3>().unwrap();
+   /// enc_key.unlock(|slice| assert_eq!(slice, &[1]));
+   /// mac_key.unlock(|slice| assert_eq!(slice, &[2, 3, 4]));
+   /// ```
+   ///
+   /// Lengths that do not add up are rejected at compile time:
+   ///
+   /// ```compile_fail
+   /// use secure_types::SecureArray;
+   ///
+   /// let master: SecureArray<u8, 4> = SecureArray::from_slice(&[1, 2, 3, 4]).unwrap();
+   /// let _ = master.split::<2, 3>();
+   /// ```
+   pub fn split<const A: usize, const B: usize>(
+      &self,
+   ) -> Result<(SecureArray<T, A>, SecureArray<T, B>), Error>
+   where
+      T: Clone,
+   {
+      const {
+         assert!(
+            A + B == LENGTH,
+            "split lengths must add up to the array length"
+         )
+      };
+
+      let first = SecureArray::<T, A>::empty_uninit()?;
+      let second = SecureArray::<T, B>::empty_uninit()?;
+
+      Ok(self.unlock(|src| {
+         (
+            first.init_from_clone(&src[..A]),
+            second.init_from_clone(&src[A..]),
+         )
+      }))
+   }
+
+   /// Creates a new array of `OUT` elements holding `self` followed by `other`.
+   ///
+   /// `LENGTH + M` must equal `OUT`, otherwise the call fails to compile.
+   pub fn concat<const M: usize, const OUT: usize>(
+      &self,
+      other: &SecureArray<T, M>,
+   ) -> Result<SecureArray<T, OUT>, Error>
+   where
+      T: Clone,
+   {
+      const {
+         assert!(
+            LENGTH + M == OUT,
+            "concat output length must equal the sum of the input lengths"
+         )
+      };
+
+      let mut out = SecureArray::<T, OUT>::empty_uninit()?;
+      self.unlock(|a| {
+         other.unlock(|b| {
+            out.unlock_mut(|dst| {
+               let mut init = InitGuard::new(dst);
+               for item in a.iter().chain(b) {
+                  init.push(item.clone());
+               }
+               init.finish();
+            })
+         })
+      });
+      Ok(unsafe { out.assume_init() })
+   }
+}
+
+impl<T, const LENGTH: usize> SecureArray<MaybeUninit<T>, LENGTH>
+where
+   T: Zeroize,
+{
+   /// Converts to `SecureArray<T, LENGTH>` without copying.
+   ///
+   /// # Safety
+   /// Every element must have been initialized.
+   pub unsafe fn assume_init(self) -> SecureArray<T, LENGTH> {
+      let ptr = self.into_raw();
+      unsafe { SecureArray::from_raw(ptr.cast()) }
+   }
+
+   /// Same as `SecureVec::init_from_clone`, for the fixed-size buffer.
+   ///
+   /// # Panics
+   /// Panics if `src.len()` is not `LENGTH`.
+   pub(crate) fn init_from_clone(mut self, src: &[T]) -> SecureArray<T, LENGTH>
+   where
+      T: Clone,
+   {
+      assert_eq!(src.len(), LENGTH);
+
+      self.unlock_mut(|slots| {
+         let mut init = InitGuard::new(slots);
+         for item in src {
+            init.push(item.clone());
+         }
+         init.finish();
+      });
+
+      unsafe { self.assume_init() }
+   }
+}
+
+impl<const LENGTH: usize> SecureArray<u8, LENGTH> {
+   /// Creates an array of random bytes from the OS random number generator
+   ///
+   /// The bytes are written by `getrandom` straight into the unlocked secure memory,
+   /// they never exist anywhere else.
+   ///
+   /// # Errors
+   /// Returns [Error::RandomFailed] if the OS random number generator fails.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::SecureArray;
+   ///
+   /// let key: SecureArray<u8, 32> = SecureArray::random().unwrap();
+   /// key.unlock(|bytes| assert_eq!(bytes.len(), 32));
+   /// ```
+   #[cfg(feature = "use_os")]
+   pub fn random() -> Result<Self, Error> {
+      let mut array = Self::zeroed()?;
+      array
+         .unlock_mut(getrandom::fill)
+         .map_err(|_| Error::RandomFailed)?;
+      Ok(array)
+   }
+
+   /// Creates an array of random bytes from the given cryptographic RNG
+   ///
+   /// The bytes are written straight into the unlocked secure memory.
+   #[cfg(feature = "rand_core")]
+   pub fn random_from_rng<R>(rng: &mut R) -> Result<Self, Error>
+   where
+      R: rand_core::CryptoRng + ?Sized,
+   {
+      let mut array = Self::zeroed()?;
+      array.unlock_mut(|bytes| rng.fill_bytes(bytes));
+      Ok(array)
    }
 }
 
@@ -341,34 +660,41 @@ impl<T: Zeroize, const LENGTH: usize> Drop for SecureArray<T, LENGTH> {
 
 impl<T: Clone + Zeroize, const LENGTH: usize> Clone for SecureArray<T, LENGTH> {
    fn clone(&self) -> Self {
-      let mut new_array = Self::empty().unwrap();
-      self.unlock(|src_slice| {
-         new_array.init_from_clone(src_slice);
-      });
-      new_array
+      let uninit = Self::empty_uninit().unwrap();
+      self.unlock(|src_slice| uninit.init_from_clone(src_slice))
    }
 }
 
-impl<const LENGTH: usize> TryFrom<SecureVec<u8>> for SecureArray<u8, LENGTH> {
+impl<T: Zeroize, const LENGTH: usize> TryFrom<SecureVec<T>> for SecureArray<T, LENGTH> {
    type Error = Error;
 
-   /// Tries to convert a `SecureVec<u8>` into a `SecureArray<u8, LENGTH>`.
+   /// Tries to convert a `SecureVec<T>` into a `SecureArray<T, LENGTH>`.
    ///
    /// This operation will only succeed if `vec.len() == LENGTH`.
    ///
-   /// The `SecureVec` is consumed.
-   fn try_from(vec: SecureVec<u8>) -> Result<Self, Self::Error> {
+   /// The `SecureVec` is consumed. If its capacity is exactly `LENGTH` the allocation is reused,
+   /// otherwise the elements are moved into a new allocation and the old one is wiped.
+   fn try_from(mut vec: SecureVec<T>) -> Result<Self, Self::Error> {
       if vec.len() != LENGTH {
          return Err(Error::LengthMismatch);
       }
 
-      let mut new_array = Self::empty()?;
+      if LENGTH * mem::size_of::<T>() == 0 {
+         return Err(Error::LengthCannotBeZero);
+      }
 
-      vec.unlock_slice(|vec_slice| {
-         new_array.init_from_clone(vec_slice);
-      });
+      if vec.capacity == LENGTH {
+         let (ptr, _, _) = vec.into_raw_parts();
+         return Ok(unsafe { SecureArray::from_raw(ptr) });
+      }
 
-      Ok(new_array)
+      let uninit = Self::empty_uninit()?;
+      {
+         let _guard = UnlockGuard::new(&uninit);
+         unsafe { vec.move_all_to(uninit.ptr.as_ptr() as *mut T) };
+      }
+
+      Ok(unsafe { uninit.assume_init() })
    }
 }
 
@@ -426,7 +752,9 @@ impl<'de, const LENGTH: usize> serde::Deserialize<'de> for SecureArray<u8, LENGT
 #[cfg(all(test, feature = "use_os"))]
 mod tests {
    use super::*;
+   use std::panic::AssertUnwindSafe;
    use std::process::{Command, Stdio};
+   use std::sync::atomic::{AtomicUsize, Ordering};
    use std::sync::{Arc, Mutex};
 
    #[test]
@@ -462,6 +790,221 @@ mod tests {
       });
    }
 
+   #[test]
+   fn test_drain_into() {
+      let mut array: SecureArray<u8, 5> = SecureArray::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+      let mut vec = SecureVec::from_slice(&[9u8]).unwrap();
+      array.drain_into(1..3, &mut vec);
+
+      array.unlock(|slice| {
+         assert_eq!(slice, &[1, 0, 0, 4, 5]);
+      });
+      vec.unlock_slice(|slice| {
+         assert_eq!(slice, &[9, 2, 3]);
+      });
+
+      let people = [create_test_person(1), create_test_person(2)];
+      let mut array = SecureArray::from_slice(&people).unwrap();
+      let mut vec = SecureVec::new().unwrap();
+      array.drain_into(.., &mut vec);
+      vec.unlock_slice(|slice| {
+         assert_eq!(slice, &people);
+      });
+   }
+
+   #[test]
+   fn test_copy_range_into() {
+      let array: SecureArray<u8, 5> = SecureArray::from_slice(&[1, 2, 3, 4, 5]).unwrap();
+      let mut vec = SecureVec::new().unwrap();
+      array.copy_range_into(3.., &mut vec);
+
+      array.unlock(|slice| {
+         assert_eq!(slice, &[1, 2, 3, 4, 5]);
+      });
+      vec.unlock_slice(|slice| {
+         assert_eq!(slice, &[4, 5]);
+      });
+   }
+
+   #[test]
+   fn test_vec_conversions_reuse_allocation() {
+      let array: SecureArray<u64, 3> = SecureArray::from_slice(&[1, 2, 3]).unwrap();
+      let ptr = array.ptr;
+
+      let vec: SecureVec<u64> = array.into();
+      vec.unlock_slice(|slice| {
+         assert_eq!(slice.as_ptr(), ptr.as_ptr() as *const u64);
+         assert_eq!(slice, &[1, 2, 3]);
+      });
+
+      let array: SecureArray<u64, 3> = vec.try_into().unwrap();
+      assert_eq!(array.ptr, ptr);
+      array.unlock(|slice| {
+         assert_eq!(slice, &[1, 2, 3]);
+      });
+   }
+
+   #[test]
+   fn test_from_secure_vec_non_copy() {
+      let mut vec = SecureVec::new_with_capacity(8).unwrap();
+      vec.push(create_test_person(1));
+      vec.push(create_test_person(2));
+
+      let array: SecureArray<Person, 2> = vec.try_into().unwrap();
+      array.unlock(|slice| {
+         assert_eq!(
+            slice,
+            &[create_test_person(1), create_test_person(2)]
+         );
+      });
+
+      let vec = SecureVec::from_slice(&[1u8, 2]).unwrap();
+      let result: Result<SecureArray<u8, 3>, _> = vec.try_into();
+      assert!(matches!(result, Err(Error::LengthMismatch)));
+   }
+
+   #[test]
+   fn test_empty_uninit_and_zeroed() {
+      let zeroed: SecureArray<u32, 4> = SecureArray::zeroed().unwrap();
+      zeroed.unlock(|slice| {
+         assert_eq!(slice, &[0u32; 4]);
+      });
+
+      let mut uninit = SecureArray::<Person, 2>::empty_uninit().unwrap();
+      uninit.unlock_mut(|slots| {
+         slots[0].write(create_test_person(1));
+         slots[1].write(create_test_person(2));
+      });
+      let people = unsafe { uninit.assume_init() };
+      people.unlock(|slice| {
+         assert_eq!(
+            slice,
+            &[create_test_person(1), create_test_person(2)]
+         );
+      });
+
+      // Dropping before `assume_init` must not touch the uninitialized slots as `T`.
+      let _uninit = SecureArray::<Person, 8>::empty_uninit().unwrap();
+   }
+
+   /// Counts its drops and can be told to panic when cloned
+   struct Tracked {
+      drops: Arc<AtomicUsize>,
+      panic_on_clone: bool,
+   }
+
+   impl Tracked {
+      fn new(drops: &Arc<AtomicUsize>, panic_on_clone: bool) -> Self {
+         Tracked {
+            drops: drops.clone(),
+            panic_on_clone,
+         }
+      }
+   }
+
+   impl Clone for Tracked {
+      fn clone(&self) -> Self {
+         assert!(!self.panic_on_clone, "clone panicked");
+         Tracked::new(&self.drops, false)
+      }
+   }
+
+   impl Zeroize for Tracked {
+      fn zeroize(&mut self) {}
+   }
+
+   impl Drop for Tracked {
+      fn drop(&mut self) {
+         self.drops.fetch_add(1, Ordering::SeqCst);
+      }
+   }
+
+   #[test]
+   fn test_panic_drops_written_slots() {
+      let drops = Arc::new(AtomicUsize::new(0));
+      let array: SecureArray<u8, 4> = SecureArray::from_slice(&[1, 2, 3, 4]).unwrap();
+
+      let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+         array.map(|&byte| {
+            assert!(byte != 3, "map panicked");
+            Tracked::new(&drops, false)
+         })
+      }));
+      assert!(result.is_err());
+      assert_eq!(drops.swap(0, Ordering::SeqCst), 2);
+
+      let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+         array.zip_with(&array, |&a, _| {
+            assert!(a != 4, "zip_with panicked");
+            Tracked::new(&drops, false)
+         })
+      }));
+      assert!(result.is_err());
+      assert_eq!(drops.swap(0, Ordering::SeqCst), 3);
+
+      let a = array.map(|_| Tracked::new(&drops, false)).unwrap();
+      let b = array.map(|&byte| Tracked::new(&drops, byte == 2)).unwrap();
+      drops.store(0, Ordering::SeqCst);
+
+      let result = std::panic::catch_unwind(AssertUnwindSafe(|| a.concat::<4, 8>(&b)));
+      assert!(result.is_err());
+      assert_eq!(drops.swap(0, Ordering::SeqCst), 5);
+
+      let result = std::panic::catch_unwind(AssertUnwindSafe(|| b.split::<2, 2>()));
+      assert!(result.is_err());
+      assert_eq!(drops.load(Ordering::SeqCst), 1);
+   }
+
+   #[test]
+   fn test_map_and_zip_with() {
+      let array: SecureArray<u8, 3> = SecureArray::from_slice(&[1, 2, 3]).unwrap();
+      let doubled: SecureArray<u16, 3> = array.map(|x| *x as u16 * 200).unwrap();
+      doubled.unlock(|slice| {
+         assert_eq!(slice, &[200, 400, 600]);
+      });
+
+      let other: SecureArray<u8, 3> = SecureArray::from_slice(&[0xFF, 0x0F, 0x00]).unwrap();
+      let xored = array.zip_with(&other, |a, b| a ^ b).unwrap();
+      xored.unlock(|slice| {
+         assert_eq!(slice, &[0xFE, 0x0D, 0x03]);
+      });
+
+      let people = array.map(|id| create_test_person(*id as usize)).unwrap();
+      people.unlock(|slice| {
+         assert_eq!(slice[2], create_test_person(3));
+      });
+   }
+
+   #[test]
+   fn test_split_and_concat() {
+      let data: [u8; 64] = core::array::from_fn(|i| i as u8);
+      let master: SecureArray<u8, 64> = SecureArray::from_slice(&data).unwrap();
+
+      let (first, second) = master.split::<32, 32>().unwrap();
+      first.unlock(|slice| {
+         assert_eq!(slice, &data[..32]);
+      });
+      second.unlock(|slice| {
+         assert_eq!(slice, &data[32..]);
+      });
+
+      let joined: SecureArray<u8, 64> = first.concat(&second).unwrap();
+      joined.unlock(|slice| {
+         assert_eq!(slice, &data);
+      });
+
+      let people =
+         SecureArray::from_slice(&[create_test_person(1), create_test_person(2)]).unwrap();
+      let (one, two) = people.split::<1, 1>().unwrap();
+      let swapped: SecureArray<Person, 2> = two.concat(&one).unwrap();
+      swapped.unlock(|slice| {
+         assert_eq!(
+            slice,
+            &[create_test_person(2), create_test_person(1)]
+         );
+      });
+   }
+
    #[test]
    fn test_erase() {
       let exposed: &mut [u8; 3] = &mut [1, 2, 3];
@@ -493,7 +1036,7 @@ mod tests {
 
    #[test]
    fn test_clone() {
-      let mut array1: SecureArray<u8, 3> = SecureArray::empty().unwrap();
+      let mut array1: SecureArray<u8, 3> = SecureArray::zeroed().unwrap();
       array1.unlock_mut(|slice| {
          slice[0] = 1;
          slice[1] = 2;
@@ -617,6 +1160,23 @@ mod tests {
       });
    }
 
+   #[test]
+   fn test_random() {
+      let a: SecureArray<u8, 32> = SecureArray::random().unwrap();
+      let b: SecureArray<u8, 32> = SecureArray::random().unwrap();
+
+      a.unlock(|bytes| assert!(bytes.iter().any(|&b| b != 0)));
+      assert!(a != b);
+   }
+
+   #[cfg(feature = "rand_core")]
+   #[test]
+   fn test_random_from_rng() {
+      let mut rng = crate::tests::CountingRng(0);
+      let array: SecureArray<u8, 5> = SecureArray::random_from_rng(&mut rng).unwrap();
+      array.unlock(|bytes| assert_eq!(bytes, &[1, 2, 3, 4, 5]));
+   }
+
    #[cfg(feature = "serde")]
    #[test]
    fn test_serde() {
@@ -679,7 +1239,7 @@ mod tests {
       }
    }
 
-   #[derive(Clone, Debug, PartialEq)]
+   #[derive(Clone, Debug, Default, PartialEq)]
    struct Person {
       name: String,
       age: u32,
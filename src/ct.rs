@@ -0,0 +1,478 @@
+use super::{SecureArray, SecureSlice, SecureSliceMut, SecureString, SecureVec};
+
+#[cfg(not(feature = "use_os"))]
+use alloc::vec::Vec;
+
+mod sealed {
+   pub trait Sealed {}
+}
+
+/// Byte containers that can take part in a constant-time comparison.
+///
+/// Implemented for [`SecureVec<u8>`], [`SecureArray<u8, LENGTH>`], [SecureString], the [SecureSlice] / [SecureSliceMut]
+/// views over bytes, and the plain `[u8]`, `[u8; LENGTH]`, `Vec<u8>` and `str` types.
+///
+/// This trait is sealed, use [CtEq] to compare.
+pub trait CtBytes: sealed::Sealed {
+   /// Gives `f` the bytes to compare, unlocking them if they are in secure memory
+   #[doc(hidden)]
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R;
+}
+
+/// Constant-time equality.
+///
+/// Every pair of [CtBytes] types can be compared, the contents are compared without any
+/// data-dependent branch or early exit so the time taken does not reveal where they differ.
+///
+/// # Length mismatch
+///
+/// The lengths are treated as public: if they differ `ct_eq` returns `false` right away.
+/// When comparing a MAC or a hash make sure both sides have a fixed size.
+///
+/// # Notes
+///
+/// Without the `subtle` feature the comparison is an XOR accumulation hidden from the optimizer
+/// with `core::hint::black_box`. With it, the comparison is done by `subtle::ConstantTimeEq`.
+///
+/// # Example
+///
+/// ```
+/// use secure_types::{CtEq, SecureArray, SecureBytes, SecureString};
+///
+/// let expected_tag: SecureArray<u8, 4> = SecureArray::from_slice(&[1, 2, 3, 4]).unwrap();
+/// let received_tag = [1u8, 2, 3, 4];
+/// assert!(expected_tag.ct_eq(&received_tag));
+///
+/// let as_vec = SecureBytes::from_slice(&[1, 2, 3, 4]).unwrap();
+/// assert!(as_vec.ct_eq(&expected_tag));
+///
+/// let password = SecureString::from("hunter2");
+/// assert!(password.ct_eq("hunter2"));
+/// assert!(!password.ct_eq("hunter3"));
+/// ```
+pub trait CtEq<Rhs: ?Sized = Self> {
+   fn ct_eq(&self, other: &Rhs) -> bool;
+}
+
+impl<A, B> CtEq<B> for A
+where
+   A: CtBytes + ?Sized,
+   B: CtBytes + ?Sized,
+{
+   fn ct_eq(&self, other: &B) -> bool {
+      self.with_ct_bytes(|a| other.with_ct_bytes(|b| ct_eq_bytes(a, b)))
+   }
+}
+
+pub(crate) fn ct_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+   if a.len() != b.len() {
+      return false;
+   }
+
+   #[cfg(feature = "subtle")]
+   {
+      bool::from(subtle::ConstantTimeEq::ct_eq(a, b))
+   }
+
+   #[cfg(not(feature = "subtle"))]
+   {
+      let mut diff = 0u8;
+      for (x, y) in a.iter().zip(b) {
+         diff |= core::hint::black_box(x ^ y);
+      }
+      core::hint::black_box(diff) == 0
+   }
+}
+
+impl sealed::Sealed for SecureVec<u8> {}
+impl CtBytes for SecureVec<u8> {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      self.unlock_slice(f)
+   }
+}
+
+impl<const LENGTH: usize> sealed::Sealed for SecureArray<u8, LENGTH> {}
+impl<const LENGTH: usize> CtBytes for SecureArray<u8, LENGTH> {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      self.unlock(|slice| f(slice))
+   }
+}
+
+impl sealed::Sealed for SecureString {}
+impl CtBytes for SecureString {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      self.unlock_str(|str| f(str.as_bytes()))
+   }
+}
+
+impl sealed::Sealed for SecureSlice<'_, u8> {}
+impl CtBytes for SecureSlice<'_, u8> {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      self.unlock(f)
+   }
+}
+
+impl sealed::Sealed for SecureSliceMut<'_, u8> {}
+impl CtBytes for SecureSliceMut<'_, u8> {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      self.unlock(f)
+   }
+}
+
+impl sealed::Sealed for [u8] {}
+impl CtBytes for [u8] {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      f(self)
+   }
+}
+
+impl<const LENGTH: usize> sealed::Sealed for [u8; LENGTH] {}
+impl<const LENGTH: usize> CtBytes for [u8; LENGTH] {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      f(self)
+   }
+}
+
+impl sealed::Sealed for Vec<u8> {}
+impl CtBytes for Vec<u8> {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      f(self)
+   }
+}
+
+impl sealed::Sealed for str {}
+impl CtBytes for str {
+   fn with_ct_bytes<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+      f(self.as_bytes())
+   }
+}
+
+/// Constant-time, see [CtEq]
+impl PartialEq for SecureVec<u8> {
+   fn eq(&self, other: &Self) -> bool {
+      CtEq::ct_eq(self, other)
+   }
+}
+
+impl Eq for SecureVec<u8> {}
+
+/// Constant-time, see [CtEq]
+impl<const LENGTH: usize> PartialEq for SecureArray<u8, LENGTH> {
+   fn eq(&self, other: &Self) -> bool {
+      CtEq::ct_eq(self, other)
+   }
+}
+
+impl<const LENGTH: usize> Eq for SecureArray<u8, LENGTH> {}
+
+/// Constant-time, see [CtEq]
+impl PartialEq for SecureString {
+   fn eq(&self, other: &Self) -> bool {
+      CtEq::ct_eq(self, other)
+   }
+}
+
+impl Eq for SecureString {}
+
+/// Constant-time operations on secret byte arrays.
+///
+/// `choice` is anything that converts into a [CtChoice], a `bool` or, with the `subtle` feature,
+/// a `subtle::Choice`. It is turned into an all-zeros or all-ones mask without branching, and every byte
+/// is written with a volatile store, so the compiler cannot turn the selection into a branch.
+impl<const LENGTH: usize> SecureArray<u8, LENGTH> {
+   /// XORs `other` into this array in place
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::SecureArray;
+   ///
+   /// let mut key: SecureArray<u8, 2> = SecureArray::from_slice(&[0b1100, 0b1010]).unwrap();
+   /// let pad: SecureArray<u8, 2> = SecureArray::from_slice(&[0b1010, 0b1010]).unwrap();
+   /// key.xor_assign(&pad);
+   ///
+   /// key.unlock(|bytes| assert_eq!(bytes, &[0b0110, 0]));
+   /// ```
+   pub fn xor_assign(&mut self, other: &Self) {
+      other.unlock(|src| self.unlock_mut(|dst| xor_bytes(dst, src)));
+   }
+
+   /// Replaces the contents with `other` if `choice` is set, leaves them untouched otherwise
+   ///
+   /// Both arrays are read and written the same way in either case.
+   pub fn conditional_assign(&mut self, other: &Self, choice: impl Into<CtChoice>) {
+      other.unlock(|src| self.unlock_mut(|dst| assign_bytes(dst, src, choice.into())));
+   }
+
+   /// Swaps the contents of the two arrays if `choice` is set
+   ///
+   /// Both arrays are read and written the same way in either case.
+   pub fn conditional_swap(&mut self, other: &mut Self, choice: impl Into<CtChoice>) {
+      other.unlock_mut(|b| self.unlock_mut(|a| swap_bytes(a, b, choice.into())));
+   }
+}
+
+/// Constant-time operations on secret byte vectors, see [SecureArray::xor_assign].
+///
+/// The lengths are treated as public and must be equal.
+impl SecureVec<u8> {
+   /// XORs `other` into this vector in place
+   ///
+   /// # Panics
+   /// Panics if the lengths differ.
+   pub fn xor_assign(&mut self, other: &Self) {
+      assert_same_len(self.len(), other.len(), "xor_assign");
+      other.unlock_slice(|src| self.unlock_slice_mut(|dst| xor_bytes(dst, src)));
+   }
+
+   /// Replaces the contents with `other` if `choice` is set, leaves them untouched otherwise
+   ///
+   /// # Panics
+   /// Panics if the lengths differ.
+   pub fn conditional_assign(&mut self, other: &Self, choice: impl Into<CtChoice>) {
+      assert_same_len(self.len(), other.len(), "conditional_assign");
+      other.unlock_slice(|src| self.unlock_slice_mut(|dst| assign_bytes(dst, src, choice.into())));
+   }
+
+   /// Swaps the contents of the two vectors if `choice` is set
+   ///
+   /// # Panics
+   /// Panics if the lengths differ.
+   pub fn conditional_swap(&mut self, other: &mut Self, choice: impl Into<CtChoice>) {
+      assert_same_len(self.len(), other.len(), "conditional_swap");
+      other.unlock_slice_mut(|b| self.unlock_slice_mut(|a| swap_bytes(a, b, choice.into())));
+   }
+}
+
+fn assert_same_len(len: usize, other_len: usize, method: &str) {
+   assert!(
+      len == other_len,
+      "SecureVec::{}: length mismatch ({} != {})",
+      method,
+      len,
+      other_len
+   );
+}
+
+/// The condition taken by `conditional_assign` and `conditional_swap`
+///
+/// Holds `0` or `1`. It is created from a `bool`, or with the `subtle` feature from a `subtle::Choice`
+/// without passing through a `bool`.
+#[derive(Clone, Copy, Debug)]
+pub struct CtChoice(u8);
+
+impl CtChoice {
+   /// Returns `1` if the choice is set and `0` otherwise
+   pub fn unwrap_u8(&self) -> u8 {
+      self.0
+   }
+}
+
+impl From<bool> for CtChoice {
+   fn from(value: bool) -> Self {
+      CtChoice(core::hint::black_box(value as u8))
+   }
+}
+
+#[cfg(feature = "subtle")]
+impl From<subtle::Choice> for CtChoice {
+   fn from(choice: subtle::Choice) -> Self {
+      CtChoice(choice.unwrap_u8())
+   }
+}
+
+/// `0x00` for an unset choice, `0xFF` for a set one, hidden from the optimizer
+fn choice_mask(choice: CtChoice) -> u8 {
+   0u8.wrapping_sub(core::hint::black_box(choice.0))
+}
+
+fn xor_bytes(dst: &mut [u8], src: &[u8]) {
+   for (d, s) in dst.iter_mut().zip(src) {
+      unsafe { core::ptr::write_volatile(d, *d ^ *s) };
+   }
+}
+
+fn assign_bytes(dst: &mut [u8], src: &[u8], choice: CtChoice) {
+   let mask = choice_mask(choice);
+   for (d, s) in dst.iter_mut().zip(src) {
+      let flip = mask & (*d ^ *s);
+      unsafe { core::ptr::write_volatile(d, *d ^ flip) };
+   }
+}
+
+fn swap_bytes(a: &mut [u8], b: &mut [u8], choice: CtChoice) {
+   let mask = choice_mask(choice);
+   for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+      let flip = mask & (*x ^ *y);
+      unsafe {
+         core::ptr::write_volatile(x, *x ^ flip);
+         core::ptr::write_volatile(y, *y ^ flip);
+      }
+   }
+}
+
+#[cfg(feature = "subtle")]
+mod subtle_impls {
+   use super::*;
+   use subtle::{Choice, ConstantTimeEq};
+
+   /// Same as [CtEq::ct_eq] but returns a `subtle::Choice`, a length mismatch is `Choice(0)`
+   fn ct_eq_choice<A: CtBytes + ?Sized, B: CtBytes + ?Sized>(a: &A, b: &B) -> Choice {
+      a.with_ct_bytes(|a| b.with_ct_bytes(|b| ConstantTimeEq::ct_eq(a, b)))
+   }
+
+   impl ConstantTimeEq for SecureVec<u8> {
+      fn ct_eq(&self, other: &Self) -> Choice {
+         ct_eq_choice(self, other)
+      }
+   }
+
+   impl<const LENGTH: usize> ConstantTimeEq for SecureArray<u8, LENGTH> {
+      fn ct_eq(&self, other: &Self) -> Choice {
+         ct_eq_choice(self, other)
+      }
+   }
+
+   impl ConstantTimeEq for SecureString {
+      fn ct_eq(&self, other: &Self) -> Choice {
+         ct_eq_choice(self, other)
+      }
+   }
+
+   impl ConstantTimeEq for SecureSlice<'_, u8> {
+      fn ct_eq(&self, other: &Self) -> Choice {
+         ct_eq_choice(self, other)
+      }
+   }
+
+   impl ConstantTimeEq for SecureSliceMut<'_, u8> {
+      fn ct_eq(&self, other: &Self) -> Choice {
+         ct_eq_choice(self, other)
+      }
+   }
+}
+
+#[cfg(all(test, feature = "use_os"))]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn test_ct_eq_between_types() {
+      let vec = SecureVec::from_slice(&[1u8, 2, 3]).unwrap();
+      let array: SecureArray<u8, 3> = SecureArray::from_slice(&[1, 2, 3]).unwrap();
+      let string = SecureString::from("\u{1}\u{2}\u{3}");
+      let plain = [1u8, 2, 3];
+
+      assert!(vec.ct_eq(&array));
+      assert!(array.ct_eq(&vec));
+      assert!(string.ct_eq(&vec));
+      assert!(vec.ct_eq(&plain));
+      assert!(vec.ct_eq(&plain[..]));
+      assert!(vec.ct_eq(&vec![1u8, 2, 3]));
+      assert!(plain.ct_eq(&array));
+      assert!(vec.slice(1..).ct_eq(&[2u8, 3]));
+      assert!(array.slice(..2).ct_eq(&vec.slice(..2)));
+
+      let mut other = SecureVec::from_slice(&[1u8, 2, 4]).unwrap();
+      assert!(!vec.ct_eq(&other));
+      assert!(vec != other);
+      assert!(vec == SecureVec::from_slice(&[1u8, 2, 3]).unwrap());
+      assert!(string == SecureString::from("\u{1}\u{2}\u{3}"));
+      assert!(other.slice_mut(..2).ct_eq(&plain[..2]));
+      assert!(!array.ct_eq(&[1u8, 2, 3, 0]));
+   }
+
+   #[test]
+   fn test_ct_eq_length_mismatch() {
+      let vec = SecureVec::from_slice(&[1u8, 2, 3]).unwrap();
+      let prefix = SecureVec::from_slice(&[1u8, 2]).unwrap();
+      assert!(!vec.ct_eq(&prefix));
+      assert!(!prefix.ct_eq(&vec));
+
+      let empty = SecureString::new().unwrap();
+      assert!(empty.ct_eq(""));
+      assert!(!empty.ct_eq("a"));
+   }
+
+   #[test]
+   fn test_array_ct_ops() {
+      let mut a: SecureArray<u8, 3> = SecureArray::from_slice(&[1, 2, 3]).unwrap();
+      let mut b: SecureArray<u8, 3> = SecureArray::from_slice(&[7, 8, 9]).unwrap();
+
+      a.conditional_assign(&b, false);
+      assert!(a.ct_eq(&[1u8, 2, 3]));
+
+      a.conditional_swap(&mut b, false);
+      assert!(a.ct_eq(&[1u8, 2, 3]));
+      assert!(b.ct_eq(&[7u8, 8, 9]));
+
+      a.conditional_swap(&mut b, true);
+      assert!(a.ct_eq(&[7u8, 8, 9]));
+      assert!(b.ct_eq(&[1u8, 2, 3]));
+
+      a.conditional_assign(&b, true);
+      assert!(a.ct_eq(&b));
+
+      a.xor_assign(&b);
+      assert!(a.ct_eq(&[0u8; 3]));
+   }
+
+   #[test]
+   fn test_vec_ct_ops() {
+      let mut a = SecureVec::from_slice(&[0xF0u8, 0x0F]).unwrap();
+      let mut b = SecureVec::from_slice(&[0xFFu8, 0xFF]).unwrap();
+
+      a.xor_assign(&b);
+      assert!(a.ct_eq(&[0x0Fu8, 0xF0]));
+
+      a.conditional_swap(&mut b, true);
+      assert!(a.ct_eq(&[0xFFu8, 0xFF]));
+      assert!(b.ct_eq(&[0x0Fu8, 0xF0]));
+
+      a.conditional_assign(&b, false);
+      assert!(a.ct_eq(&[0xFFu8, 0xFF]));
+      a.conditional_assign(&b, true);
+      assert!(a.ct_eq(&[0x0Fu8, 0xF0]));
+   }
+
+   #[test]
+   #[should_panic(expected = "length mismatch")]
+   fn test_vec_ct_ops_length_mismatch() {
+      let mut a = SecureVec::from_slice(&[1u8, 2]).unwrap();
+      let b = SecureVec::from_slice(&[1u8]).unwrap();
+      a.xor_assign(&b);
+   }
+
+   #[cfg(feature = "subtle")]
+   #[test]
+   fn test_subtle_constant_time_eq() {
+      use subtle::ConstantTimeEq;
+
+      let a: SecureArray<u8, 2> = SecureArray::from_slice(&[7, 8]).unwrap();
+      let b: SecureArray<u8, 2> = SecureArray::from_slice(&[7, 8]).unwrap();
+      let c: SecureArray<u8, 2> = SecureArray::from_slice(&[7, 9]).unwrap();
+      assert!(bool::from(ConstantTimeEq::ct_eq(&a, &b)));
+      assert!(!bool::from(ConstantTimeEq::ct_eq(&a, &c)));
+
+      let mut x: SecureArray<u8, 2> = SecureArray::from_slice(&[1, 2]).unwrap();
+      x.conditional_assign(&a, subtle::Choice::from(0));
+      assert!(CtEq::ct_eq(&x, &[1u8, 2]));
+      x.conditional_assign(&a, ConstantTimeEq::ct_eq(&b, &a));
+      assert!(CtEq::ct_eq(&x, &[7u8, 8]));
+
+      let short = SecureString::from("pass");
+      let long = SecureString::from("password");
+      assert!(!bool::from(ConstantTimeEq::ct_eq(&short, &long)));
+
+      let mut vec = SecureVec::from_slice(&[7u8, 8, 9]).unwrap();
+      let mut other = SecureVec::from_slice(&[7u8, 8, 0]).unwrap();
+      assert!(bool::from(ConstantTimeEq::ct_eq(
+         &vec.slice_mut(..2),
+         &other.slice_mut(..2)
+      )));
+      assert!(!bool::from(ConstantTimeEq::ct_eq(
+         &vec.slice_mut(1..),
+         &other.slice_mut(1..)
+      )));
+   }
+}
@@ -0,0 +1,612 @@
+use super::{Error, SecureString, SecureVec};
+use zeroize::Zeroize;
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"0123456789";
+const SYMBOLS: &[u8] = b"!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// Chars that are easy to confuse with each other in many fonts
+const AMBIGUOUS: &[u8] = b"0Oo1lI|";
+
+const ALL_CLASSES: [CharClass; 4] = [
+   CharClass::Lowercase,
+   CharClass::Uppercase,
+   CharClass::Digits,
+   CharClass::Symbols,
+];
+
+/// The largest alphabet a [PasswordPolicy] can produce
+const MAX_ALPHABET: usize = LOWERCASE.len() + UPPERCASE.len() + DIGITS.len() + SYMBOLS.len();
+
+/// A class of ASCII chars a generated password can draw from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharClass {
+   /// `a-z`
+   Lowercase,
+   /// `A-Z`
+   Uppercase,
+   /// `0-9`
+   Digits,
+   /// The 32 ASCII punctuation chars, like `!`, `#` and `~`
+   Symbols,
+}
+
+impl CharClass {
+   fn chars(self) -> &'static [u8] {
+      match self {
+         CharClass::Lowercase => LOWERCASE,
+         CharClass::Uppercase => UPPERCASE,
+         CharClass::Digits => DIGITS,
+         CharClass::Symbols => SYMBOLS,
+      }
+   }
+
+   fn bit(self) -> u8 {
+      1 << self as u8
+   }
+
+   fn contains(self, byte: u8) -> bool {
+      match self {
+         CharClass::Lowercase => byte.is_ascii_lowercase(),
+         CharClass::Uppercase => byte.is_ascii_uppercase(),
+         CharClass::Digits => byte.is_ascii_digit(),
+         CharClass::Symbols => byte.is_ascii_punctuation(),
+      }
+   }
+}
+
+/// How [SecureString::generate] builds a random password.
+///
+/// By default every [CharClass] is used, none of them is required
+/// and ambiguous chars like `0` and `O` are allowed.
+///
+/// # Example
+///
+/// ```
+/// use secure_types::{CharClass, PasswordPolicy, SecureString};
+///
+/// let policy = PasswordPolicy::new(20)
+///    .without(CharClass::Symbols)
+///    .require(CharClass::Digits)
+///    .exclude_ambiguous(true);
+///
+/// assert!(policy.entropy_bits() > 110.0);
+///
+/// let password = SecureString::generate(policy).unwrap();
+/// password.unlock_str(|str| {
+///    assert_eq!(str.len(), 20);
+///    assert!(str.bytes().any(|b| b.is_ascii_digit()));
+///    assert!(str.bytes().all(|b| b.is_ascii_alphanumeric() && b != b'0' && b != b'O'));
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PasswordPolicy {
+   length: usize,
+   classes: u8,
+   required: u8,
+   exclude_ambiguous: bool,
+}
+
+impl PasswordPolicy {
+   /// A policy for passwords of `length` chars
+   pub fn new(length: usize) -> Self {
+      PasswordPolicy {
+         length,
+         classes: ALL_CLASSES.iter().fold(0, |bits, class| bits | class.bit()),
+         required: 0,
+         exclude_ambiguous: false,
+      }
+   }
+
+   /// Draws chars from `class`
+   pub fn with(mut self, class: CharClass) -> Self {
+      self.classes |= class.bit();
+      self
+   }
+
+   /// Never draws chars from `class`, this also drops it from the required classes
+   pub fn without(mut self, class: CharClass) -> Self {
+      self.classes &= !class.bit();
+      self.required &= !class.bit();
+      self
+   }
+
+   /// Every password contains at least one char from `class`
+   pub fn require(mut self, class: CharClass) -> Self {
+      self.classes |= class.bit();
+      self.required |= class.bit();
+      self
+   }
+
+   /// Leaves out the chars `0 O o 1 l I |`
+   pub fn exclude_ambiguous(mut self, exclude: bool) -> Self {
+      self.exclude_ambiguous = exclude;
+      self
+   }
+
+   /// Returns the number of chars in the generated passwords
+   pub fn length(&self) -> usize {
+      self.length
+   }
+
+   /// Returns the entropy of the generated passwords in bits
+   ///
+   /// Passwords that miss a required class are redrawn, so the result accounts for them
+   /// being impossible instead of assuming every char is independent.
+   ///
+   /// Returns `0.0` if the policy cannot be satisfied.
+   pub fn entropy_bits(&self) -> f64 {
+      if self.validate().is_err() {
+         return 0.0;
+      }
+
+      let alphabet = self.alphabet().1 as f64;
+      let length = self.length as f64;
+
+      // Inclusion-exclusion over the required classes gives the fraction of
+      // uniformly drawn passwords that contain every one of them.
+      let mut fraction = 0.0;
+      for missing in 0..=self.required {
+         if missing & !self.required != 0 {
+            continue;
+         }
+
+         let missing_chars: usize = ALL_CLASSES
+            .iter()
+            .filter(|class| missing & class.bit() != 0)
+            .map(|class| self.class_len(*class))
+            .sum();
+         let term = (1.0 - missing_chars as f64 / alphabet).powf(length);
+
+         if missing.count_ones() % 2 == 0 {
+            fraction += term;
+         } else {
+            fraction -= term;
+         }
+      }
+
+      length * alphabet.log2() + fraction.log2()
+   }
+
+   fn validate(&self) -> Result<(), Error> {
+      if self.length == 0 {
+         return Err(Error::LengthCannotBeZero);
+      }
+      if self.classes == 0 || self.required.count_ones() as usize > self.length {
+         return Err(Error::InvalidPolicy);
+      }
+      Ok(())
+   }
+
+   fn is_allowed(&self, byte: u8) -> bool {
+      !(self.exclude_ambiguous && AMBIGUOUS.contains(&byte))
+   }
+
+   fn class_len(&self, class: CharClass) -> usize {
+      class
+         .chars()
+         .iter()
+         .filter(|byte| self.is_allowed(**byte))
+         .count()
+   }
+
+   fn alphabet(&self) -> ([u8; MAX_ALPHABET], usize) {
+      let mut alphabet = [0u8; MAX_ALPHABET];
+      let mut len = 0;
+
+      for class in ALL_CLASSES
+         .iter()
+         .filter(|class| self.classes & class.bit() != 0)
+      {
+         for &byte in class.chars().iter().filter(|byte| self.is_allowed(**byte)) {
+            alphabet[len] = byte;
+            len += 1;
+         }
+      }
+
+      (alphabet, len)
+   }
+
+   fn has_required(&self, password: &[u8]) -> bool {
+      ALL_CLASSES
+         .iter()
+         .filter(|class| self.required & class.bit() != 0)
+         .all(|class| password.iter().any(|byte| class.contains(*byte)))
+   }
+}
+
+/// How [SecureString::generate] builds a random passphrase from a word list.
+///
+/// The entropy assumes the words in the list are distinct,
+/// a diceware list of 7776 words gives about 12.9 bits per word.
+///
+/// # Example
+///
+/// ```
+/// use secure_types::{PassphrasePolicy, SecureString};
+///
+/// let words = ["correct", "horse", "battery", "staple"];
+/// let policy = PassphrasePolicy::new(&words, 6).separator("-");
+/// assert_eq!(policy.entropy_bits(), 12.0);
+///
+/// let passphrase = SecureString::generate(policy).unwrap();
+/// passphrase.unlock_str(|str| {
+///    assert_eq!(str.split('-').count(), 6);
+///    assert!(str.split('-').all(|word| words.contains(&word)));
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PassphrasePolicy<'a> {
+   words: &'a [&'a str],
+   count: usize,
+   separator: &'a str,
+}
+
+impl<'a> PassphrasePolicy<'a> {
+   /// A policy for passphrases of `count` words from `words`, separated by a space
+   pub fn new(words: &'a [&'a str], count: usize) -> Self {
+      PassphrasePolicy {
+         words,
+         count,
+         separator: " ",
+      }
+   }
+
+   /// Separates the words with `separator` instead of a space
+   pub fn separator(mut self, separator: &'a str) -> Self {
+      self.separator = separator;
+      self
+   }
+
+   /// Returns the number of words in the generated passphrases
+   pub fn count(&self) -> usize {
+      self.count
+   }
+
+   /// Returns the entropy of the generated passphrases in bits
+   ///
+   /// Returns `0.0` if the policy cannot be satisfied.
+   pub fn entropy_bits(&self) -> f64 {
+      if self.validate().is_err() {
+         return 0.0;
+      }
+      self.count as f64 * (self.words.len() as f64).log2()
+   }
+
+   fn validate(&self) -> Result<(), Error> {
+      if self.count == 0 {
+         return Err(Error::LengthCannotBeZero);
+      }
+      if self.words.len() < 2 {
+         return Err(Error::InvalidPolicy);
+      }
+      Ok(())
+   }
+}
+
+/// A policy for [SecureString::generate], either a password or a passphrase
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy<'a> {
+   Password(PasswordPolicy),
+   Passphrase(PassphrasePolicy<'a>),
+}
+
+impl Policy<'_> {
+   /// Returns the entropy of the generated secrets in bits
+   pub fn entropy_bits(&self) -> f64 {
+      match self {
+         Policy::Password(policy) => policy.entropy_bits(),
+         Policy::Passphrase(policy) => policy.entropy_bits(),
+      }
+   }
+}
+
+impl From<PasswordPolicy> for Policy<'_> {
+   fn from(policy: PasswordPolicy) -> Self {
+      Policy::Password(policy)
+   }
+}
+
+impl<'a> From<PassphrasePolicy<'a>> for Policy<'a> {
+   fn from(policy: PassphrasePolicy<'a>) -> Self {
+      Policy::Passphrase(policy)
+   }
+}
+
+/// Where the random bytes come from
+trait RandomSource {
+   fn fill(&mut self, dest: &mut [u8]) -> Result<(), Error>;
+}
+
+struct OsRandom;
+
+impl RandomSource for OsRandom {
+   fn fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+      getrandom::fill(dest).map_err(|_| Error::RandomFailed)
+   }
+}
+
+#[cfg(feature = "rand_core")]
+struct CryptoRngSource<'r, R: ?Sized>(&'r mut R);
+
+#[cfg(feature = "rand_core")]
+impl<R: rand_core::CryptoRng + ?Sized> RandomSource for CryptoRngSource<'_, R> {
+   fn fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+      self.0.fill_bytes(dest);
+      Ok(())
+   }
+}
+
+/// Returns a uniformly distributed index below `n`
+fn random_index(rng: &mut dyn RandomSource, n: usize) -> Result<usize, Error> {
+   let n = n as u64;
+   // Values at or above the last multiple of `n` are redrawn to avoid modulo bias
+   let limit = u64::MAX - u64::MAX % n;
+
+   loop {
+      let mut bytes = [0u8; 8];
+      rng.fill(&mut bytes)?;
+      let mut value = u64::from_le_bytes(bytes);
+      bytes.zeroize();
+
+      let index = (value < limit).then(|| (value % n) as usize);
+      value.zeroize();
+
+      if let Some(index) = index {
+         return Ok(index);
+      }
+   }
+}
+
+fn generate_password(
+   policy: &PasswordPolicy,
+   rng: &mut dyn RandomSource,
+) -> Result<SecureString, Error> {
+   policy.validate()?;
+   let (alphabet, len) = policy.alphabet();
+   let alphabet = &alphabet[..len];
+
+   let mut vec = SecureVec::zeroed(policy.length)?;
+   vec.unlock_slice_mut(|password| {
+      loop {
+         for slot in password.iter_mut() {
+            *slot = alphabet[random_index(rng, alphabet.len())?];
+         }
+
+         if policy.has_required(password) {
+            return Ok(());
+         }
+      }
+   })?;
+
+   // Every byte comes from the ASCII alphabet
+   Ok(unsafe { SecureString::from_utf8_unchecked(vec) })
+}
+
+fn generate_passphrase(
+   policy: &PassphrasePolicy<'_>,
+   rng: &mut dyn RandomSource,
+) -> Result<SecureString, Error> {
+   policy.validate()?;
+
+   // Reserve enough up front so the passphrase never has to grow
+   let longest = policy
+      .words
+      .iter()
+      .map(|word| word.len())
+      .max()
+      .unwrap_or(0);
+   let capacity = policy.count * longest + (policy.count - 1) * policy.separator.len();
+   let mut passphrase = SecureString::new_with_capacity(capacity)?;
+
+   for i in 0..policy.count {
+      if i > 0 {
+         passphrase.push_str(policy.separator);
+      }
+
+      let mut index = random_index(rng, policy.words.len())?;
+      passphrase.push_str(policy.words[index]);
+      index.zeroize();
+   }
+
+   Ok(passphrase)
+}
+
+fn generate(policy: Policy<'_>, rng: &mut dyn RandomSource) -> Result<SecureString, Error> {
+   match policy {
+      Policy::Password(policy) => generate_password(&policy, rng),
+      Policy::Passphrase(policy) => generate_passphrase(&policy, rng),
+   }
+}
+
+impl SecureString {
+   /// Generates a random password or passphrase with the OS random number generator
+   ///
+   /// The chars are written straight into the secure memory,
+   /// use [`Policy::entropy_bits`] to tell how strong the result is.
+   ///
+   /// # Errors
+   /// - [Error::LengthCannotBeZero] if the length or word count is zero
+   /// - [Error::InvalidPolicy] if no class is enabled, more classes are required than
+   ///   the length allows, or the word list has fewer than 2 words
+   /// - [Error::RandomFailed] if the OS random number generator fails
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::{PasswordPolicy, SecureString};
+   ///
+   /// let password = SecureString::generate(PasswordPolicy::new(16)).unwrap();
+   /// assert_eq!(password.char_len(), 16);
+   /// ```
+   pub fn generate<'a>(policy: impl Into<Policy<'a>>) -> Result<SecureString, Error> {
+      generate(policy.into(), &mut OsRandom)
+   }
+
+   /// Same as [`generate`](Self::generate) but with the given cryptographic RNG
+   #[cfg(feature = "rand_core")]
+   pub fn generate_from_rng<'a, R>(
+      policy: impl Into<Policy<'a>>,
+      rng: &mut R,
+   ) -> Result<SecureString, Error>
+   where
+      R: rand_core::CryptoRng + ?Sized,
+   {
+      generate(policy.into(), &mut CryptoRngSource(rng))
+   }
+}
+
+#[cfg(all(test, feature = "use_os"))]
+mod tests {
+   use super::*;
+
+   fn assert_close(a: f64, b: f64) {
+      assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+   }
+
+   #[test]
+   fn test_password_length_and_classes() {
+      let policy = PasswordPolicy::new(64).without(CharClass::Symbols);
+      let password = SecureString::generate(policy).unwrap();
+
+      assert_eq!(password.byte_len(), 64);
+      password.unlock_str(|str| assert!(str.bytes().all(|b| b.is_ascii_alphanumeric())));
+
+      let policy = PasswordPolicy::new(64)
+         .without(CharClass::Lowercase)
+         .without(CharClass::Uppercase)
+         .without(CharClass::Symbols);
+      let pin = SecureString::generate(policy).unwrap();
+      pin.unlock_str(|str| assert!(str.bytes().all(|b| b.is_ascii_digit())));
+   }
+
+   #[test]
+   fn test_required_classes() {
+      let policy = PasswordPolicy::new(4)
+         .require(CharClass::Lowercase)
+         .require(CharClass::Uppercase)
+         .require(CharClass::Digits)
+         .require(CharClass::Symbols);
+
+      for _ in 0..50 {
+         let password = SecureString::generate(policy).unwrap();
+         password.unlock_str(|str| {
+            for class in ALL_CLASSES {
+               assert!(
+                  str.bytes().any(|b| class.contains(b)),
+                  "{:?}",
+                  class
+               );
+            }
+         });
+      }
+   }
+
+   #[test]
+   fn test_exclude_ambiguous() {
+      let policy = PasswordPolicy::new(500).exclude_ambiguous(true);
+      let password = SecureString::generate(policy).unwrap();
+      password.unlock_str(|str| assert!(str.bytes().all(|b| !AMBIGUOUS.contains(&b))));
+   }
+
+   #[test]
+   fn test_entropy() {
+      assert_close(
+         PasswordPolicy::new(16).entropy_bits(),
+         16.0 * 94f64.log2(),
+      );
+      assert_close(
+         PasswordPolicy::new(16)
+            .exclude_ambiguous(true)
+            .entropy_bits(),
+         16.0 * 87f64.log2(),
+      );
+
+      // Only the 10 single char passwords that are a digit are possible
+      assert_close(
+         PasswordPolicy::new(1)
+            .require(CharClass::Digits)
+            .entropy_bits(),
+         10f64.log2(),
+      );
+
+      // 2 char passwords with a digit and a letter: 10 * 52 in either order
+      let policy = PasswordPolicy::new(2)
+         .without(CharClass::Symbols)
+         .require(CharClass::Digits)
+         .require(CharClass::Lowercase);
+      assert_close(
+         policy.entropy_bits(),
+         (2.0 * 10.0 * 26.0f64).log2(),
+      );
+
+      let words = ["a", "b", "c", "d", "e", "f", "g", "h"];
+      let policy = PassphrasePolicy::new(&words, 5);
+      assert_close(policy.entropy_bits(), 15.0);
+      assert_close(Policy::from(policy).entropy_bits(), 15.0);
+   }
+
+   #[test]
+   fn test_invalid_policies() {
+      assert!(matches!(
+         SecureString::generate(PasswordPolicy::new(0)),
+         Err(Error::LengthCannotBeZero)
+      ));
+
+      let no_classes = ALL_CLASSES
+         .iter()
+         .fold(PasswordPolicy::new(8), |policy, class| {
+            policy.without(*class)
+         });
+      assert!(matches!(
+         SecureString::generate(no_classes),
+         Err(Error::InvalidPolicy)
+      ));
+      assert_eq!(no_classes.entropy_bits(), 0.0);
+
+      let too_short = PasswordPolicy::new(1)
+         .require(CharClass::Digits)
+         .require(CharClass::Symbols);
+      assert!(matches!(
+         SecureString::generate(too_short),
+         Err(Error::InvalidPolicy)
+      ));
+
+      let words = ["only"];
+      assert!(matches!(
+         SecureString::generate(PassphrasePolicy::new(&words, 4)),
+         Err(Error::InvalidPolicy)
+      ));
+      assert!(matches!(
+         SecureString::generate(PassphrasePolicy::new(&["a", "b"], 0)),
+         Err(Error::LengthCannotBeZero)
+      ));
+   }
+
+   #[test]
+   fn test_passphrase() {
+      let words = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"];
+      let policy = PassphrasePolicy::new(&words, 5).separator(".");
+      let passphrase = SecureString::generate(policy).unwrap();
+
+      // Room for 5 of the longest word and 4 separators is reserved up front
+      assert_eq!(passphrase.capacity(), 5 * 7 + 4);
+      passphrase.unlock_str(|str| {
+         let parts: Vec<&str> = str.split('.').collect();
+         assert_eq!(parts.len(), 5);
+         assert!(parts.iter().all(|word| words.contains(word)));
+      });
+   }
+
+   #[cfg(feature = "rand_core")]
+   #[test]
+   fn test_generate_from_rng_is_deterministic() {
+      let policy = PasswordPolicy::new(24).require(CharClass::Symbols);
+
+      let a = SecureString::generate_from_rng(policy, &mut crate::tests::CountingRng(0)).unwrap();
+      let b = SecureString::generate_from_rng(policy, &mut crate::tests::CountingRng(0)).unwrap();
+      assert!(a == b);
+      assert_eq!(a.byte_len(), 24);
+   }
+}
@@ -0,0 +1,219 @@
+use super::{SecureArray, SecureString, SecureVec, ct::ct_eq_bytes};
+use std::{
+   borrow::Borrow,
+   collections::hash_map::RandomState,
+   hash::{BuildHasher, Hash, Hasher},
+   sync::OnceLock,
+};
+
+/// Process-wide random SipHash key, drawn the first time a secret is hashed
+static HASH_KEY: OnceLock<RandomState> = OnceLock::new();
+
+/// SipHash of `bytes` under the process-random key
+///
+/// Only this digest is fed to the caller's hasher, so even a fixed-key hasher
+/// produces values that are meaningless in any other process.
+fn keyed_digest(bytes: &[u8]) -> u64 {
+   HASH_KEY.get_or_init(RandomState::new).hash_one(bytes)
+}
+
+/// A key that can be looked up in a `HashMap` or `HashSet` keyed by secure types.
+///
+/// [`SecureVec<u8>`](crate::SecureVec), [`SecureArray<u8, LENGTH>`](crate::SecureArray) and [SecureString] implement `Borrow<dyn HashKey>`,
+/// so a map can be queried with any `HashKey`, such as a `&str` or a `&[u8]`,
+/// without building a secure copy of the key first.
+///
+/// The bytes are hashed with the same process-random key and compared in constant time.
+/// The key comes from std's `RandomState`, so hashing is only available with the `use_os` feature.
+///
+/// # Example
+///
+/// ```
+/// use secure_types::{HashKey, SecureString};
+/// use std::collections::HashMap;
+///
+/// let mut vault = HashMap::new();
+/// vault.insert(SecureString::from("alice"), 1);
+/// vault.insert(SecureString::from("bob"), 2);
+///
+/// assert_eq!(vault.get(&"alice" as &dyn HashKey), Some(&1));
+/// assert_eq!(vault.get(&SecureString::from("bob")), Some(&2));
+/// assert_eq!(vault.get(&"carol" as &dyn HashKey), None);
+/// ```
+pub trait HashKey {
+   /// Gives `f` the key bytes, unlocking them if they are in secure memory
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8]));
+}
+
+impl Hash for dyn HashKey + '_ {
+   fn hash<H: Hasher>(&self, state: &mut H) {
+      let mut digest = 0;
+      self.with_key_bytes(&mut |bytes| digest = keyed_digest(bytes));
+      state.write_u64(digest);
+   }
+}
+
+impl PartialEq for dyn HashKey + '_ {
+   fn eq(&self, other: &Self) -> bool {
+      let mut equal = false;
+      self.with_key_bytes(&mut |a| {
+         other.with_key_bytes(&mut |b| equal = ct_eq_bytes(a, b));
+      });
+      equal
+   }
+}
+
+impl Eq for dyn HashKey + '_ {}
+
+impl HashKey for SecureVec<u8> {
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8])) {
+      self.unlock_slice(f)
+   }
+}
+
+impl<const LENGTH: usize> HashKey for SecureArray<u8, LENGTH> {
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8])) {
+      self.unlock(|slice| f(slice))
+   }
+}
+
+impl HashKey for SecureString {
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8])) {
+      self.unlock_str(|str| f(str.as_bytes()))
+   }
+}
+
+impl HashKey for &[u8] {
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8])) {
+      f(self)
+   }
+}
+
+impl<const LENGTH: usize> HashKey for [u8; LENGTH] {
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8])) {
+      f(self)
+   }
+}
+
+impl HashKey for Vec<u8> {
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8])) {
+      f(self)
+   }
+}
+
+impl HashKey for &str {
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8])) {
+      f(self.as_bytes())
+   }
+}
+
+impl HashKey for String {
+   fn with_key_bytes(&self, f: &mut dyn FnMut(&[u8])) {
+      f(self.as_bytes())
+   }
+}
+
+impl Hash for SecureVec<u8> {
+   fn hash<H: Hasher>(&self, state: &mut H) {
+      (self as &dyn HashKey).hash(state);
+   }
+}
+
+impl<const LENGTH: usize> Hash for SecureArray<u8, LENGTH> {
+   fn hash<H: Hasher>(&self, state: &mut H) {
+      (self as &dyn HashKey).hash(state);
+   }
+}
+
+impl Hash for SecureString {
+   fn hash<H: Hasher>(&self, state: &mut H) {
+      (self as &dyn HashKey).hash(state);
+   }
+}
+
+impl<'a> Borrow<dyn HashKey + 'a> for SecureVec<u8> {
+   fn borrow(&self) -> &(dyn HashKey + 'a) {
+      self
+   }
+}
+
+impl<'a, const LENGTH: usize> Borrow<dyn HashKey + 'a> for SecureArray<u8, LENGTH> {
+   fn borrow(&self) -> &(dyn HashKey + 'a) {
+      self
+   }
+}
+
+impl<'a> Borrow<dyn HashKey + 'a> for SecureString {
+   fn borrow(&self) -> &(dyn HashKey + 'a) {
+      self
+   }
+}
+
+// The unlock count is behind a mutex, but it never takes part in `Hash` or `Eq`
+#[cfg(all(test, feature = "use_os"))]
+#[allow(clippy::mutable_key_type)]
+mod tests {
+   use super::*;
+   use std::collections::{HashMap, HashSet};
+
+   fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+      let mut hasher = std::collections::hash_map::DefaultHasher::new();
+      value.hash(&mut hasher);
+      hasher.finish()
+   }
+
+   #[test]
+   fn test_hash_is_consistent_across_types() {
+      let string = SecureString::from("key");
+      let vec = SecureVec::from_slice(b"key").unwrap();
+      let array: SecureArray<u8, 3> = SecureArray::from_slice(b"key").unwrap();
+
+      assert_eq!(hash_of(&string), hash_of(&vec));
+      assert_eq!(hash_of(&vec), hash_of(&array));
+      assert_eq!(hash_of(&string), hash_of(&"key" as &dyn HashKey));
+      assert_eq!(
+         hash_of(&string),
+         hash_of(&&b"key"[..] as &dyn HashKey)
+      );
+      assert_ne!(
+         hash_of(&string),
+         hash_of(&SecureString::from("kez"))
+      );
+
+      // Only the keyed digest reaches the outer hasher
+      assert_ne!(hash_of(&string), hash_of(b"key".as_slice()));
+   }
+
+   #[test]
+   fn test_hash_set_dedup() {
+      let mut set = HashSet::new();
+      assert!(set.insert(SecureString::from("hunter2")));
+      assert!(set.insert(SecureString::from("hunter3")));
+      assert!(!set.insert(SecureString::from("hunter2")));
+      assert_eq!(set.len(), 2);
+
+      assert!(set.contains(&"hunter3" as &dyn HashKey));
+      assert!(!set.contains(&"hunter4" as &dyn HashKey));
+      assert!(set.contains(&String::from("hunter2") as &dyn HashKey));
+   }
+
+   #[test]
+   fn test_map_lookup_by_bytes() {
+      let mut map: HashMap<SecureArray<u8, 4>, &str> = HashMap::new();
+      map.insert(
+         SecureArray::from_slice(&[1, 2, 3, 4]).unwrap(),
+         "first",
+      );
+
+      assert_eq!(
+         map.get(&[1u8, 2, 3, 4] as &dyn HashKey),
+         Some(&"first")
+      );
+      assert_eq!(map.get(&&[1u8, 2, 3][..] as &dyn HashKey), None);
+
+      let mut map: HashMap<SecureVec<u8>, u32> = HashMap::new();
+      map.insert(SecureVec::from_slice(&[9u8, 9]).unwrap(), 99);
+      assert_eq!(map.remove(&[9u8, 9] as &dyn HashKey), Some(99));
+      assert!(map.is_empty());
+   }
+}
@@ -7,11 +7,35 @@
 extern crate alloc;
 
 pub mod array;
+pub mod ct;
+#[cfg(feature = "use_os")]
+pub mod generate;
+#[cfg(feature = "use_os")]
+pub mod hash;
+pub mod redact;
+pub mod slice;
 pub mod string;
+#[cfg(all(feature = "use_os", unix))]
+pub mod terminal;
+pub mod text;
+#[cfg(feature = "egui")]
+pub mod text_buffer;
 pub mod vec;
 
 pub use array::SecureArray;
+pub use ct::{CtChoice, CtEq};
+#[cfg(feature = "use_os")]
+pub use generate::{CharClass, PassphrasePolicy, PasswordPolicy, Policy};
+#[cfg(feature = "use_os")]
+pub use hash::HashKey;
+pub use redact::{Redaction, redaction, set_redaction};
+pub use slice::{SecureSlice, SecureSliceMut};
+#[cfg(feature = "segmentation")]
+pub use string::CursorPosition;
 pub use string::SecureString;
+pub use text::SecureText;
+#[cfg(feature = "egui")]
+pub use text_buffer::SecureTextBuffer;
 pub use vec::{SecureBytes, SecureVec};
 
 use core::ptr::NonNull;
@@ -45,6 +69,20 @@ pub enum Error {
    LengthMismatch,
    #[error("Bytes are not valid UTF-8")]
    InvalidUtf8,
+   #[error("Code units are not valid UTF-16")]
+   InvalidUtf16,
+   #[error("String is not allowed by the PRECIS profile")]
+   PrecisDisallowed,
+   #[error("Fixed capacity exceeded")]
+   CapacityExceeded,
+   #[error("Failed to generate random bytes")]
+   RandomFailed,
+   #[error("Generation policy cannot be satisfied")]
+   InvalidPolicy,
+   #[error("Failed to read from the terminal")]
+   TerminalFailed,
+   #[error("Input was interrupted")]
+   Interrupted,
 }
 
 #[cfg(not(feature = "use_os"))]
@@ -58,6 +96,9 @@ pub enum Error {
    UnlockFailed,
    LengthMismatch,
    InvalidUtf8,
+   InvalidUtf16,
+   PrecisDisallowed,
+   CapacityExceeded,
 }
 
 #[cfg(all(feature = "use_os", unix))]
@@ -278,9 +319,83 @@ pub(crate) fn mprotect<T>(ptr: NonNull<T>, prot: Prot::Ty) -> bool {
    }
 }
 
+/// Counts the active unlocks of a secure allocation.
+///
+/// A [SecureSlice] unlocks the same pages as its parent, so the parent and all of its windows
+/// share one count and only the last unlock to finish locks the memory again.
+/// The count is behind a mutex so an unlock on one thread cannot race a relock on another.
+///
+/// The mutex is boxed so it keeps its address when the owning buffer is moved.
+#[derive(Default)]
+pub(crate) struct UnlockCount {
+   #[cfg(feature = "use_os")]
+   active: Box<std::sync::Mutex<usize>>,
+}
+
+impl UnlockCount {
+   #[cfg(feature = "use_os")]
+   fn active(&self) -> std::sync::MutexGuard<'_, usize> {
+      self.active.lock().unwrap_or_else(|e| e.into_inner())
+   }
+
+   /// Runs `unlock` if no other unlock is active
+   pub(crate) fn acquire(&self, unlock: impl FnOnce() -> bool) -> bool {
+      #[cfg(feature = "use_os")]
+      {
+         let mut active = self.active();
+         let ok = if *active == 0 { unlock() } else { true };
+         *active += 1;
+         ok
+      }
+      #[cfg(not(feature = "use_os"))]
+      {
+         unlock()
+      }
+   }
+
+   /// Runs `lock` if this was the last active unlock
+   pub(crate) fn release(&self, lock: impl FnOnce() -> bool) -> bool {
+      #[cfg(feature = "use_os")]
+      {
+         let mut active = self.active();
+         *active -= 1;
+         if *active == 0 { lock() } else { true }
+      }
+      #[cfg(not(feature = "use_os"))]
+      {
+         lock()
+      }
+   }
+}
+
 #[cfg(test)]
 mod tests {
 
+   /// A predictable RNG that yields 1, 2, 3, ... for the `rand_core` tests
+   #[cfg(feature = "rand_core")]
+   pub(crate) struct CountingRng(pub(crate) u8);
+
+   #[cfg(feature = "rand_core")]
+   impl rand_core::RngCore for CountingRng {
+      fn next_u32(&mut self) -> u32 {
+         rand_core::impls::next_u32_via_fill(self)
+      }
+
+      fn next_u64(&mut self) -> u64 {
+         rand_core::impls::next_u64_via_fill(self)
+      }
+
+      fn fill_bytes(&mut self, dst: &mut [u8]) {
+         for byte in dst {
+            self.0 = self.0.wrapping_add(1);
+            *byte = self.0;
+         }
+      }
+   }
+
+   #[cfg(feature = "rand_core")]
+   impl rand_core::CryptoRng for CountingRng {}
+
    #[cfg(unix)]
    #[test]
    fn test_supports_memfd_secret() {
@@ -0,0 +1,302 @@
+use super::{SecureArray, SecureSlice, SecureSliceMut, SecureString, SecureText, SecureVec};
+use core::{
+   fmt::{self, Debug, Display, Formatter, Write},
+   sync::atomic::{AtomicU8, Ordering},
+};
+use zeroize::Zeroize;
+
+/// How the `Debug` and `Display` impls of the secure types print their redacted contents.
+///
+/// The style is process-wide, set it once at startup with [set_redaction].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Redaction {
+   /// `SecureString([REDACTED; 12 bytes])`
+   #[default]
+   WithLength,
+   /// `SecureString([REDACTED])`, the length is not revealed either
+   Opaque,
+}
+
+const REDACTION_WITH_LENGTH: u8 = 0;
+const REDACTION_OPAQUE: u8 = 1;
+
+static REDACTION: AtomicU8 = AtomicU8::new(REDACTION_WITH_LENGTH);
+
+/// Sets the redaction style used by every `Debug` and `Display` impl of the secure types
+///
+/// # Example
+///
+/// ```
+/// use secure_types::{Redaction, SecureString, set_redaction};
+///
+/// let password = SecureString::from("hunter2");
+/// assert_eq!(format!("{:?}", password), "SecureString([REDACTED; 7 bytes])");
+///
+/// set_redaction(Redaction::Opaque);
+/// assert_eq!(format!("{:?}", password), "SecureString([REDACTED])");
+/// assert_eq!(password.to_string(), "[REDACTED]");
+/// ```
+pub fn set_redaction(style: Redaction) {
+   let value = match style {
+      Redaction::WithLength => REDACTION_WITH_LENGTH,
+      Redaction::Opaque => REDACTION_OPAQUE,
+   };
+   REDACTION.store(value, Ordering::Relaxed);
+}
+
+/// Returns the current redaction style
+pub fn redaction() -> Redaction {
+   match REDACTION.load(Ordering::Relaxed) {
+      REDACTION_OPAQUE => Redaction::Opaque,
+      _ => Redaction::WithLength,
+   }
+}
+
+/// The `[REDACTED; 12 bytes]` placeholder printed in place of the contents
+struct Redacted {
+   len: usize,
+   unit: &'static str,
+}
+
+impl Redacted {
+   fn bytes(len: usize) -> Self {
+      Redacted { len, unit: "bytes" }
+   }
+
+   fn elements(len: usize) -> Self {
+      Redacted {
+         len,
+         unit: "elements",
+      }
+   }
+}
+
+impl Debug for Redacted {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      Display::fmt(self, f)
+   }
+}
+
+impl Display for Redacted {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      match redaction() {
+         Redaction::WithLength => write!(f, "[REDACTED; {} {}]", self.len, self.unit),
+         Redaction::Opaque => f.write_str("[REDACTED]"),
+      }
+   }
+}
+
+impl<T: Zeroize> Debug for SecureVec<T> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_tuple("SecureVec")
+         .field(&Redacted::elements(self.len()))
+         .finish()
+   }
+}
+
+impl<T: Zeroize, const LENGTH: usize> Debug for SecureArray<T, LENGTH> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_tuple("SecureArray")
+         .field(&Redacted::elements(LENGTH))
+         .finish()
+   }
+}
+
+impl<T: Zeroize> Debug for SecureSlice<'_, T> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_tuple("SecureSlice")
+         .field(&Redacted::elements(self.len()))
+         .finish()
+   }
+}
+
+impl<T: Zeroize> Debug for SecureSliceMut<'_, T> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_tuple("SecureSliceMut")
+         .field(&Redacted::elements(self.len()))
+         .finish()
+   }
+}
+
+impl Debug for SecureString {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_tuple("SecureString")
+         .field(&Redacted::bytes(self.byte_len()))
+         .finish()
+   }
+}
+
+impl Display for SecureString {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      Display::fmt(&Redacted::bytes(self.byte_len()), f)
+   }
+}
+
+impl Debug for SecureText {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      f.debug_tuple("SecureText")
+         .field(&Redacted::bytes(self.byte_len()))
+         .finish()
+   }
+}
+
+impl Display for SecureText {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      Display::fmt(&Redacted::bytes(self.byte_len()), f)
+   }
+}
+
+/// A masked view of a [SecureString] for showing it in a UI, like `••••••2345`.
+///
+/// It is created by [SecureString::masked]. Every char is replaced with the mask char,
+/// except for the last few if [`show_last`](Self::show_last) is used.
+///
+/// # Notes
+///
+/// The visible chars are written to the formatter in plain text, so only use it
+/// for values where that is acceptable, like the last digits of a card number.
+pub struct Masked<'a> {
+   string: &'a SecureString,
+   mask: char,
+   visible: usize,
+}
+
+impl Masked<'_> {
+   /// Shows the last `count` chars in plain text
+   pub fn show_last(mut self, count: usize) -> Self {
+      self.visible = count;
+      self
+   }
+
+   /// Uses `mask` instead of `•`
+   pub fn mask_char(mut self, mask: char) -> Self {
+      self.mask = mask;
+      self
+   }
+}
+
+impl Display for Masked<'_> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      self.string.unlock_str(|s| {
+         let char_len = s.chars().count();
+         let masked = char_len - self.visible.min(char_len);
+         for _ in 0..masked {
+            f.write_char(self.mask)?;
+         }
+
+         let visible_start = s.char_indices().nth(masked).map_or(s.len(), |(idx, _)| idx);
+         f.write_str(&s[visible_start..])
+      })
+   }
+}
+
+impl Debug for Masked<'_> {
+   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+      Display::fmt(self, f)
+   }
+}
+
+impl SecureString {
+   /// Returns a formatter that prints the string masked, for UI use
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::SecureString;
+   ///
+   /// let card = SecureString::from("4111111111112345");
+   /// assert_eq!(card.masked().show_last(4).to_string(), "••••••••••••2345");
+   /// assert_eq!(card.masked().mask_char('*').to_string(), "*".repeat(16));
+   /// ```
+   pub fn masked(&self) -> Masked<'_> {
+      Masked {
+         string: self,
+         mask: '•',
+         visible: 0,
+      }
+   }
+}
+
+#[cfg(all(test, feature = "use_os"))]
+mod tests {
+   use super::*;
+
+   // The redaction style is process-wide, so the tests that depend on it run in one test.
+   #[test]
+   fn test_redacted_debug_and_display() {
+      let string = SecureString::from("hunter2🦀");
+      let vec = SecureVec::from_slice(&[1u8, 2, 3]).unwrap();
+      let array: SecureArray<u8, 4> = SecureArray::from_slice(&[1, 2, 3, 4]).unwrap();
+      let text = SecureText::from(SecureString::from("notes"));
+
+      assert_eq!(
+         format!("{:?}", string),
+         "SecureString([REDACTED; 11 bytes])"
+      );
+      assert_eq!(string.to_string(), "[REDACTED; 11 bytes]");
+      assert_eq!(
+         format!("{:?}", vec),
+         "SecureVec([REDACTED; 3 elements])"
+      );
+      assert_eq!(
+         format!("{:?}", vec.slice(1..)),
+         "SecureSlice([REDACTED; 2 elements])"
+      );
+      assert_eq!(
+         format!("{:?}", array),
+         "SecureArray([REDACTED; 4 elements])"
+      );
+      assert_eq!(
+         format!("{:?}", text),
+         "SecureText([REDACTED; 5 bytes])"
+      );
+      assert_eq!(text.to_string(), "[REDACTED; 5 bytes]");
+
+      #[derive(Debug)]
+      #[allow(dead_code)]
+      struct Credentials {
+         user: &'static str,
+         password: SecureString,
+      }
+      let credentials = Credentials {
+         user: "admin",
+         password: string.clone(),
+      };
+      let debug = format!("{:?}", credentials);
+      assert!(!debug.contains("hunter2"));
+      assert!(debug.contains("password: SecureString([REDACTED; 11 bytes])"));
+
+      set_redaction(Redaction::Opaque);
+      assert_eq!(redaction(), Redaction::Opaque);
+      assert_eq!(
+         format!("{:?}", string),
+         "SecureString([REDACTED])"
+      );
+      assert_eq!(
+         format!("{:#?}", vec),
+         "SecureVec(\n    [REDACTED],\n)"
+      );
+      assert_eq!(string.to_string(), "[REDACTED]");
+
+      set_redaction(Redaction::WithLength);
+      assert_eq!(redaction(), Redaction::WithLength);
+   }
+
+   #[test]
+   fn test_masked() {
+      let secret = SecureString::from("pässwörd");
+      assert_eq!(secret.masked().to_string(), "••••••••");
+      assert_eq!(
+         secret.masked().show_last(3).to_string(),
+         "•••••örd"
+      );
+      assert_eq!(
+         secret.masked().show_last(20).mask_char('*').to_string(),
+         "pässwörd"
+      );
+      assert_eq!(format!("{:?}", secret.masked()), "••••••••");
+
+      let empty = SecureString::new().unwrap();
+      assert_eq!(empty.masked().show_last(4).to_string(), "");
+   }
+}
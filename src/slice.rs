@@ -0,0 +1,423 @@
+use super::UnlockCount;
+use super::vec::resolve_range_indices;
+use core::{marker::PhantomData, ops::RangeBounds, ptr::NonNull};
+use zeroize::Zeroize;
+
+#[cfg(feature = "use_os")]
+use memsec::Prot;
+
+/// Unlocks the parent allocation on construction and re-locks it on drop —
+/// including when the drop happens because the fn closure panicked.
+///
+/// The parent's unlock count is shared by every window, so the memory is only
+/// locked again once the last unlock of the parent or any of its windows ends.
+struct UnlockGuard<'a, T> {
+   base: NonNull<T>,
+   unlocks: &'a UnlockCount,
+}
+
+impl<'a, T> UnlockGuard<'a, T> {
+   fn new(base: NonNull<T>, unlocks: &'a UnlockCount) -> Self {
+      let ok = unlocks.acquire(|| unlock_memory(base));
+      debug_assert!(ok, "UnlockGuard::new: unlock_memory failed");
+      UnlockGuard { base, unlocks }
+   }
+}
+
+impl<T> Drop for UnlockGuard<'_, T> {
+   fn drop(&mut self) {
+      let ok = self.unlocks.release(|| lock_memory(self.base));
+      debug_assert!(ok, "UnlockGuard::drop: lock_memory failed");
+   }
+}
+
+fn lock_memory<T>(_base: NonNull<T>) -> bool {
+   #[cfg(feature = "use_os")]
+   {
+      super::mprotect(_base, Prot::NoAccess)
+   }
+   #[cfg(not(feature = "use_os"))]
+   {
+      true // No-op: always "succeeds"
+   }
+}
+
+fn unlock_memory<T>(_base: NonNull<T>) -> bool {
+   #[cfg(feature = "use_os")]
+   {
+      super::mprotect(_base, Prot::ReadWrite)
+   }
+   #[cfg(not(feature = "use_os"))]
+   {
+      true // No-op: always "succeeds"
+   }
+}
+
+/// A borrowed, read-only window into a [SecureVec](crate::SecureVec) or [SecureArray](crate::SecureArray).
+///
+/// It is created by `SecureVec::slice` and `SecureArray::slice`.
+///
+/// The view does not copy anything, it shares the parent's lock state: the memory stays locked
+/// while the view exists and [`unlock`](Self::unlock) only hands out the window, not the whole buffer.
+///
+/// # Notes
+///
+/// Memory protection works at page granularity, so while the window is unlocked
+/// the pages of the whole parent allocation are readable.
+///
+/// # Example
+///
+/// ```
+/// use secure_types::SecureBytes;
+///
+/// let secret = SecureBytes::from_slice(&[1, 2, 3, 4, 5, 6]).unwrap();
+/// let first_half = secret.slice(..3);
+///
+/// first_half.unlock(|window| {
+///     assert_eq!(window, &[1, 2, 3]);
+/// });
+/// ```
+pub struct SecureSlice<'a, T: Zeroize> {
+   base: NonNull<T>,
+   unlocks: &'a UnlockCount,
+   offset: usize,
+   len: usize,
+   _marker: PhantomData<&'a [T]>,
+}
+
+unsafe impl<'a, T: Zeroize + Sync> Send for SecureSlice<'a, T> {}
+unsafe impl<'a, T: Zeroize + Sync> Sync for SecureSlice<'a, T> {}
+
+impl<'a, T: Zeroize> Clone for SecureSlice<'a, T> {
+   fn clone(&self) -> Self {
+      *self
+   }
+}
+
+impl<'a, T: Zeroize> Copy for SecureSlice<'a, T> {}
+
+impl<'a, T: Zeroize> SecureSlice<'a, T> {
+   /// # Safety
+   /// `base` must be the start of a secure allocation that outlives `'a`
+   /// and holds at least `offset + len` initialized elements,
+   /// `unlocks` must be the unlock count of that allocation.
+   pub(crate) unsafe fn new(
+      base: NonNull<T>,
+      unlocks: &'a UnlockCount,
+      offset: usize,
+      len: usize,
+   ) -> Self {
+      SecureSlice {
+         base,
+         unlocks,
+         offset,
+         len,
+         _marker: PhantomData,
+      }
+   }
+
+   pub fn len(&self) -> usize {
+      self.len
+   }
+
+   pub fn is_empty(&self) -> bool {
+      self.len() == 0
+   }
+
+   /// Returns a narrower view, `range` is relative to this window
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds of this window.
+   pub fn slice<R>(&self, range: R) -> SecureSlice<'a, T>
+   where
+      R: RangeBounds<usize>,
+   {
+      let (start, end) = resolve_range_indices(range, self.len);
+      unsafe {
+         SecureSlice::new(
+            self.base,
+            self.unlocks,
+            self.offset + start,
+            end - start,
+         )
+      }
+   }
+
+   /// Immutable access to the window as `&[T]`
+   pub fn unlock<F, R>(&self, f: F) -> R
+   where
+      F: FnOnce(&[T]) -> R,
+   {
+      let _guard = UnlockGuard::new(self.base, self.unlocks);
+      let slice =
+         unsafe { core::slice::from_raw_parts(self.base.as_ptr().add(self.offset), self.len) };
+      f(slice)
+   }
+}
+
+/// A borrowed, mutable window into a [SecureVec](crate::SecureVec) or [SecureArray](crate::SecureArray).
+///
+/// It is created by `SecureVec::slice_mut` and `SecureArray::slice_mut`.
+///
+/// Same as [SecureSlice] but it also provides [`unlock_mut`](Self::unlock_mut).
+pub struct SecureSliceMut<'a, T: Zeroize> {
+   base: NonNull<T>,
+   unlocks: &'a UnlockCount,
+   offset: usize,
+   len: usize,
+   _marker: PhantomData<&'a mut [T]>,
+}
+
+unsafe impl<'a, T: Zeroize + Send> Send for SecureSliceMut<'a, T> {}
+unsafe impl<'a, T: Zeroize + Sync> Sync for SecureSliceMut<'a, T> {}
+
+impl<'a, T: Zeroize> SecureSliceMut<'a, T> {
+   /// # Safety
+   /// Same as [SecureSlice::new], and the caller must hold exclusive access to the window for `'a`.
+   pub(crate) unsafe fn new(
+      base: NonNull<T>,
+      unlocks: &'a UnlockCount,
+      offset: usize,
+      len: usize,
+   ) -> Self {
+      SecureSliceMut {
+         base,
+         unlocks,
+         offset,
+         len,
+         _marker: PhantomData,
+      }
+   }
+
+   pub fn len(&self) -> usize {
+      self.len
+   }
+
+   pub fn is_empty(&self) -> bool {
+      self.len() == 0
+   }
+
+   /// Returns a read-only view of a part of this window, `range` is relative to this window
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds of this window.
+   pub fn slice<R>(&self, range: R) -> SecureSlice<'_, T>
+   where
+      R: RangeBounds<usize>,
+   {
+      let (start, end) = resolve_range_indices(range, self.len);
+      unsafe {
+         SecureSlice::new(
+            self.base,
+            self.unlocks,
+            self.offset + start,
+            end - start,
+         )
+      }
+   }
+
+   /// Returns a narrower mutable view, `range` is relative to this window
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds of this window.
+   pub fn slice_mut<R>(&mut self, range: R) -> SecureSliceMut<'_, T>
+   where
+      R: RangeBounds<usize>,
+   {
+      let (start, end) = resolve_range_indices(range, self.len);
+      unsafe {
+         SecureSliceMut::new(
+            self.base,
+            self.unlocks,
+            self.offset + start,
+            end - start,
+         )
+      }
+   }
+
+   /// Immutable access to the window as `&[T]`
+   pub fn unlock<F, R>(&self, f: F) -> R
+   where
+      F: FnOnce(&[T]) -> R,
+   {
+      let _guard = UnlockGuard::new(self.base, self.unlocks);
+      let slice =
+         unsafe { core::slice::from_raw_parts(self.base.as_ptr().add(self.offset), self.len) };
+      f(slice)
+   }
+
+   /// Mutable access to the window as `&mut [T]`
+   pub fn unlock_mut<F, R>(&mut self, f: F) -> R
+   where
+      F: FnOnce(&mut [T]) -> R,
+   {
+      let _guard = UnlockGuard::new(self.base, self.unlocks);
+      let slice =
+         unsafe { core::slice::from_raw_parts_mut(self.base.as_ptr().add(self.offset), self.len) };
+      f(slice)
+   }
+}
+
+#[cfg(all(test, feature = "use_os"))]
+mod tests {
+   use crate::{SecureArray, SecureVec};
+   use std::process::{Command, Stdio};
+
+   #[test]
+   fn test_vec_slice() {
+      let vec = SecureVec::from_slice(&[1u8, 2, 3, 4, 5, 6]).unwrap();
+      let window = vec.slice(2..5);
+      assert_eq!(window.len(), 3);
+
+      window.unlock(|slice| {
+         assert_eq!(slice, &[3, 4, 5]);
+      });
+
+      let narrower = window.slice(1..);
+      narrower.unlock(|slice| {
+         assert_eq!(slice, &[4, 5]);
+      });
+
+      assert!(vec.slice(6..).is_empty());
+   }
+
+   #[test]
+   fn test_vec_slice_mut() {
+      let mut vec = SecureVec::from_slice(&[1u16, 2, 3, 4]).unwrap();
+      let mut window = vec.slice_mut(1..3);
+      window.unlock_mut(|slice| {
+         slice[0] = 20;
+         slice[1] = 30;
+      });
+      window.slice_mut(1..).unlock_mut(|slice| {
+         slice[0] += 1;
+      });
+
+      vec.unlock_slice(|slice| {
+         assert_eq!(slice, &[1, 20, 31, 4]);
+      });
+   }
+
+   #[test]
+   fn test_array_slice() {
+      let mut array: SecureArray<u8, 64> = SecureArray::from_slice(&[7u8; 64]).unwrap();
+      array.slice_mut(32..).unlock_mut(|slice| {
+         slice.fill(9);
+      });
+
+      let (first, second) = (array.slice(..32), array.slice(32..));
+      first.unlock(|slice| {
+         assert_eq!(slice, &[7u8; 32]);
+      });
+      second.unlock(|slice| {
+         assert_eq!(slice, &[9u8; 32]);
+      });
+   }
+
+   #[test]
+   #[should_panic]
+   fn test_slice_out_of_bounds() {
+      let vec = SecureVec::from_slice(&[1u8, 2, 3]).unwrap();
+      let _window = vec.slice(1..4);
+   }
+
+   #[test]
+   fn test_nested_sibling_windows() {
+      let array: SecureArray<u8, 64> = SecureArray::from_slice(&[7u8; 64]).unwrap();
+      let (key, salt) = (array.slice(..32), array.slice(32..));
+
+      // The inner unlock ends first, the outer window must still be readable after it
+      let first = key.unlock(|key| {
+         salt.unlock(|salt| assert_eq!(salt.len(), 32));
+         key[0]
+      });
+      assert_eq!(first, 7);
+
+      // Same with the parent and a window of it
+      let vec = SecureVec::from_slice(&[1u8, 2, 3, 4]).unwrap();
+      let sum = vec.unlock_slice(|all| {
+         let tail = vec.slice(2..).unlock(|tail| tail[0] + tail[1]);
+         all[0] + tail
+      });
+      assert_eq!(sum, 8);
+   }
+
+   #[test]
+   fn test_windows_unlocked_from_two_threads() {
+      let array: SecureArray<u8, 64> = SecureArray::from_slice(&[3u8; 64]).unwrap();
+      let (key, salt) = (array.slice(..32), array.slice(32..));
+
+      std::thread::scope(|scope| {
+         for window in [key, salt] {
+            scope.spawn(move || {
+               for _ in 0..2_000 {
+                  let sum: u32 = window.unlock(|bytes| bytes.iter().map(|&b| b as u32).sum());
+                  assert_eq!(sum, 3 * 32);
+               }
+            });
+         }
+      });
+
+      // Every unlock has ended, so the memory is locked again
+      array.unlock(|bytes| assert_eq!(bytes[0], 3));
+   }
+
+   #[test]
+   fn test_memory_is_locked_after_slice_unlock() {
+      let arg = "CRASH_TEST_SECURESLICE_LOCKED";
+
+      if std::env::args().any(|a| a == arg) {
+         let vec = SecureVec::from_slice(&[1u8, 2, 3]).unwrap();
+         let window = vec.slice(1..);
+         window.unlock(|slice| {
+            assert_eq!(slice, &[2, 3]);
+         });
+         // The parent must be locked again once the window is released.
+         let _value = unsafe { core::hint::black_box(*window.base.as_ptr()) };
+
+         std::process::exit(1);
+      }
+
+      let child = Command::new(std::env::current_exe().unwrap())
+         .arg("slice::tests::test_memory_is_locked_after_slice_unlock")
+         .arg(arg)
+         .arg("--nocapture")
+         .stdout(Stdio::piped())
+         .stderr(Stdio::piped())
+         .spawn()
+         .expect("Failed to spawn child process");
+
+      let output = child.wait_with_output().expect("Failed to wait on child");
+      let status = output.status;
+
+      assert!(
+         !status.success(),
+         "Process exited successfully with code {:?}, but it should have crashed.",
+         status.code()
+      );
+
+      #[cfg(unix)]
+      {
+         use std::os::unix::process::ExitStatusExt;
+         let signal = status
+            .signal()
+            .expect("Process was not terminated by a signal on Unix.");
+         assert!(
+            signal == libc::SIGSEGV || signal == libc::SIGBUS,
+            "Process terminated with unexpected signal: {}",
+            signal
+         );
+      }
+
+      #[cfg(windows)]
+      {
+         const STATUS_ACCESS_VIOLATION: i32 = 0xC0000005_u32 as i32;
+         assert_eq!(
+            status.code(),
+            Some(STATUS_ACCESS_VIOLATION),
+            "Process exited with unexpected code: {:x?}. Expected STATUS_ACCESS_VIOLATION.",
+            status.code()
+         );
+      }
+   }
+}
@@ -0,0 +1,356 @@
+use super::{Error, SecureString, SecureVec};
+use std::{
+   fs::OpenOptions,
+   io,
+   os::fd::{AsFd, AsRawFd, RawFd},
+};
+use zeroize::Zeroize;
+
+const CTRL_C: u8 = 0x03;
+const CTRL_D: u8 = 0x04;
+const BACKSPACE: u8 = 0x08;
+const CTRL_U: u8 = 0x15;
+const DELETE: u8 = 0x7f;
+
+/// Turns off echo and line editing on a terminal until it is dropped
+///
+/// The original settings are restored in `Drop`, so they are also restored
+/// when a panic unwinds through the read.
+struct NoEchoGuard {
+   fd: RawFd,
+   original: libc::termios,
+}
+
+impl NoEchoGuard {
+   fn new(fd: RawFd) -> Result<Self, Error> {
+      let mut original = unsafe { core::mem::zeroed::<libc::termios>() };
+      if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+         return Err(Error::TerminalFailed);
+      }
+
+      // Without ICANON the bytes arrive one by one and the line editing is done here,
+      // without ISIG Ctrl-C arrives as a byte instead of killing the process with the terminal still silent.
+      let mut no_echo = original;
+      no_echo.c_lflag &= !(libc::ECHO | libc::ECHONL | libc::ICANON | libc::ISIG | libc::IEXTEN);
+      no_echo.c_cc[libc::VMIN] = 1;
+      no_echo.c_cc[libc::VTIME] = 0;
+
+      if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &no_echo) } != 0 {
+         return Err(Error::TerminalFailed);
+      }
+
+      Ok(NoEchoGuard { fd, original })
+   }
+}
+
+impl Drop for NoEchoGuard {
+   fn drop(&mut self) {
+      unsafe {
+         libc::tcsetattr(self.fd, libc::TCSANOW, &self.original);
+      }
+   }
+}
+
+/// Reads a single byte, returns `false` at the end of the input
+fn read_byte(fd: RawFd, byte: &mut u8) -> Result<bool, Error> {
+   loop {
+      let read = unsafe { libc::read(fd, byte as *mut u8 as *mut libc::c_void, 1) };
+      match read {
+         1 => return Ok(true),
+         0 => return Ok(false),
+         _ if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted => continue,
+         _ => return Err(Error::TerminalFailed),
+      }
+   }
+}
+
+fn write_all(fd: RawFd, mut bytes: &[u8]) -> Result<(), Error> {
+   while !bytes.is_empty() {
+      let written = unsafe {
+         libc::write(
+            fd,
+            bytes.as_ptr() as *const libc::c_void,
+            bytes.len(),
+         )
+      };
+      if written > 0 {
+         bytes = &bytes[written as usize..];
+      } else if io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+         return Err(Error::TerminalFailed);
+      }
+   }
+   Ok(())
+}
+
+/// Removes the last UTF-8 char, the input arrives a byte at a time so it may span several bytes
+fn erase_last_char(password: &mut SecureVec<u8>) {
+   let start = password.unlock_slice(|bytes| {
+      bytes
+         .iter()
+         .rposition(|byte| byte & 0xC0 != 0x80)
+         .unwrap_or(0)
+   });
+   password.drain(start..);
+}
+
+/// Reads until Enter, Ctrl-D or the end of the input, straight into `password`
+fn read_line_into(fd: RawFd, password: &mut SecureVec<u8>, byte: &mut u8) -> Result<(), Error> {
+   while read_byte(fd, byte)? {
+      match *byte {
+         b'\n' | b'\r' | CTRL_D => break,
+         CTRL_C => return Err(Error::Interrupted),
+         BACKSPACE | DELETE => erase_last_char(password),
+         CTRL_U => password.erase(),
+         _ => password.push(*byte),
+      }
+   }
+   Ok(())
+}
+
+/// Reads until `\n` or the end of the input without any line editing, a trailing `\r` is removed
+fn read_raw_line_into(fd: RawFd, password: &mut SecureVec<u8>, byte: &mut u8) -> Result<(), Error> {
+   while read_byte(fd, byte)? && *byte != b'\n' {
+      password.push(*byte);
+   }
+
+   let len = password.len();
+   if password.unlock_slice(|bytes| bytes.last() == Some(&b'\r')) {
+      password.drain(len - 1..);
+   }
+   Ok(())
+}
+
+/// Reads a line with the terminal line editing when `edit` is set, or as is otherwise
+fn read_line(fd: RawFd, edit: bool) -> Result<SecureString, Error> {
+   let mut password = SecureVec::new()?;
+   let mut byte = 0u8;
+   let result = if edit {
+      read_line_into(fd, &mut password, &mut byte)
+   } else {
+      read_raw_line_into(fd, &mut password, &mut byte)
+   };
+   byte.zeroize();
+
+   result?;
+   password.into_string()
+}
+
+impl SecureString {
+   /// Prompts for a password on the controlling terminal and reads it without echo
+   ///
+   /// Like [`read_password_from_fd`](Self::read_password_from_fd) on `/dev/tty`,
+   /// so it works even when stdin and stdout are redirected.
+   ///
+   /// # Errors
+   /// - [Error::TerminalFailed] if there is no controlling terminal or it cannot be read
+   /// - [Error::Interrupted] if Ctrl-C is pressed
+   /// - [Error::InvalidUtf8] if the input is not valid UTF-8
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use secure_types::SecureString;
+   ///
+   /// let password = SecureString::read_password("Password: ").unwrap();
+   /// ```
+   pub fn read_password(prompt: &str) -> Result<SecureString, Error> {
+      let tty = OpenOptions::new()
+         .read(true)
+         .write(true)
+         .open("/dev/tty")
+         .map_err(|_| Error::TerminalFailed)?;
+      Self::read_password_from_fd(&tty, prompt)
+   }
+
+   /// Writes `prompt` to `fd` and reads a password from it without echo
+   ///
+   /// The bytes are read one at a time straight into secure memory. Backspace removes the last char,
+   /// Ctrl-U clears the line, and Enter or Ctrl-D ends the input. Ctrl-C stops reading,
+   /// erases what was typed and returns [Error::Interrupted] instead of killing the process.
+   ///
+   /// The terminal settings are restored before returning, and also if a panic unwinds through the read.
+   ///
+   /// If `fd` is not a terminal, like a pipe, the prompt is not written and
+   /// a single line is read as is: every byte up to `\n` is kept, only a trailing `\r` is removed.
+   ///
+   /// # Errors
+   /// See [`read_password`](Self::read_password)
+   pub fn read_password_from_fd(fd: impl AsFd, prompt: &str) -> Result<SecureString, Error> {
+      let fd = fd.as_fd().as_raw_fd();
+
+      if unsafe { libc::isatty(fd) } != 1 {
+         return read_line(fd, false);
+      }
+
+      // Turn echo off before the prompt so nothing typed right after it is echoed
+      let guard = NoEchoGuard::new(fd)?;
+      write_all(fd, prompt.as_bytes())?;
+      let password = read_line(fd, true);
+      drop(guard);
+
+      // Enter was not echoed, so move to the next line ourselves
+      let _ = write_all(fd, b"\n");
+      password
+   }
+}
+
+#[cfg(all(test, feature = "use_os"))]
+mod tests {
+   use super::*;
+   use std::{
+      fs::File,
+      io::{Read, Write},
+      os::fd::{FromRawFd, OwnedFd},
+      thread,
+      time::{Duration, Instant},
+   };
+
+   struct Pty {
+      master: File,
+      slave: OwnedFd,
+   }
+
+   fn open_pty() -> Pty {
+      let mut master = -1;
+      let mut slave = -1;
+      let result = unsafe {
+         libc::openpty(
+            &mut master,
+            &mut slave,
+            core::ptr::null_mut(),
+            core::ptr::null(),
+            core::ptr::null(),
+         )
+      };
+      assert_eq!(result, 0, "openpty failed");
+
+      unsafe {
+         Pty {
+            master: File::from_raw_fd(master),
+            slave: OwnedFd::from_raw_fd(slave),
+         }
+      }
+   }
+
+   fn lflag(fd: RawFd) -> libc::tcflag_t {
+      let mut termios = unsafe { core::mem::zeroed::<libc::termios>() };
+      assert_eq!(unsafe { libc::tcgetattr(fd, &mut termios) }, 0);
+      termios.c_lflag
+   }
+
+   /// Types `input` once the reader has turned echo off and returns its result
+   /// together with everything it wrote to the terminal
+   fn type_password(input: &[u8]) -> (Result<SecureString, Error>, String) {
+      let mut pty = open_pty();
+      let slave_fd = pty.slave.as_raw_fd();
+      assert_ne!(lflag(slave_fd) & libc::ECHO, 0);
+
+      let slave = pty.slave.try_clone().unwrap();
+      let reader = thread::spawn(move || SecureString::read_password_from_fd(&slave, "Password: "));
+
+      let start = Instant::now();
+      while lflag(slave_fd) & libc::ECHO != 0 {
+         assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "echo was never disabled"
+         );
+         thread::sleep(Duration::from_millis(1));
+      }
+
+      pty.master.write_all(input).unwrap();
+      let result = reader.join().unwrap();
+
+      // Echo and line editing are back on
+      let lflag = lflag(slave_fd);
+      assert_ne!(lflag & libc::ECHO, 0);
+      assert_ne!(lflag & libc::ICANON, 0);
+      assert_ne!(lflag & libc::ISIG, 0);
+
+      // Closing the slave makes the master return what is left and then end
+      drop(pty.slave);
+      let mut output = Vec::new();
+      let mut buf = [0u8; 256];
+      while let Ok(n) = pty.master.read(&mut buf) {
+         if n == 0 {
+            break;
+         }
+         output.extend_from_slice(&buf[..n]);
+      }
+
+      (result, String::from_utf8(output).unwrap())
+   }
+
+   #[test]
+   fn test_read_password_without_echo() {
+      let (password, output) = type_password(b"hunter2\r");
+      let password = password.unwrap();
+
+      password.unlock_str(|str| assert_eq!(str, "hunter2"));
+      assert_eq!(output, "Password: \r\n");
+   }
+
+   #[test]
+   fn test_line_editing() {
+      let (password, output) = type_password(b"hunter3\x7f2\n");
+      password
+         .unwrap()
+         .unlock_str(|str| assert_eq!(str, "hunter2"));
+      assert!(!output.contains("hunter"));
+
+      let (password, _) = type_password("pä\x08a🦀\x7f\x7fss\x04".as_bytes());
+      password.unwrap().unlock_str(|str| assert_eq!(str, "pss"));
+
+      let (password, _) = type_password(b"wrong\x15right\n");
+      password.unwrap().unlock_str(|str| assert_eq!(str, "right"));
+   }
+
+   #[test]
+   fn test_ctrl_c_interrupts() {
+      let (password, output) = type_password(b"hunt\x03er2\n");
+      assert!(matches!(password, Err(Error::Interrupted)));
+      assert!(!output.contains("hunt"));
+   }
+
+   #[test]
+   fn test_terminal_restored_on_panic() {
+      let pty = open_pty();
+      let fd = pty.slave.as_raw_fd();
+
+      let result = std::panic::catch_unwind(|| {
+         let _guard = NoEchoGuard::new(fd).unwrap();
+         assert_eq!(lflag(fd) & libc::ECHO, 0);
+         panic!("panic while reading");
+      });
+
+      assert!(result.is_err());
+      assert_ne!(lflag(fd) & libc::ECHO, 0);
+      assert_ne!(lflag(fd) & libc::ICANON, 0);
+   }
+
+   #[test]
+   fn test_read_password_from_pipe() {
+      let mut fds = [0; 2];
+      assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+      let (reader, mut writer) = unsafe {
+         (
+            OwnedFd::from_raw_fd(fds[0]),
+            File::from_raw_fd(fds[1]),
+         )
+      };
+
+      writer
+         .write_all(b"from\x7f a\x08 pipe\x15\x03\x04\r\nnext line")
+         .unwrap();
+      drop(writer);
+
+      // The terminal editing keys are part of the secret when it is piped in
+      let password = SecureString::read_password_from_fd(&reader, "not shown").unwrap();
+      password.unlock_str(|str| assert_eq!(str, "from\x7f a\x08 pipe\x15\x03\x04"));
+   }
+
+   #[test]
+   fn test_invalid_utf8() {
+      let (password, _) = type_password(b"\xff\xfe\n");
+      assert!(matches!(password, Err(Error::InvalidUtf8)));
+   }
+}
@@ -0,0 +1,465 @@
+use super::{Error, SecureString, SecureVec};
+use core::ops::Range;
+use zeroize::Zeroize;
+
+/// A securely allocated UTF-8 text buffer for editing large secrets, such as a PEM bundle or a notes vault.
+///
+/// [SecureString] keeps its bytes contiguous, so every edit shifts the whole tail and
+/// every char index is resolved by scanning from the start of the string.
+///
+/// `SecureText` is a gap buffer instead: the free space is kept at the last edit position,
+/// and the gap doubles as a char index to byte index cache because the number of chars
+/// before it is always known. Edits near the cursor are therefore amortized O(1),
+/// moving the cursor costs O(distance).
+///
+/// The gap is always zeroized, so no stale copy of moved or deleted text is left behind.
+///
+/// # Example
+///
+/// ```
+/// use secure_types::{SecureString, SecureText};
+///
+/// let mut notes = SecureText::from(SecureString::from("vault: "));
+///
+/// // Typing at the cursor only writes into the gap
+/// notes.insert(7, "ghp_");
+/// notes.insert(11, "token");
+/// notes.delete(0..7);
+///
+/// // The gap now sits where the last edit happened
+/// notes.unlock_parts(|before, after| {
+///     assert_eq!(before, "");
+///     assert_eq!(after, "ghp_token");
+/// });
+///
+/// let string = notes.into_secure_string();
+/// string.unlock_str(|str| assert_eq!(str, "ghp_token"));
+/// ```
+pub struct SecureText {
+   /// Fully initialized, `len == capacity`, the text lives in `[0, gap_start)` and `[gap_end, len)`
+   buf: SecureVec<u8>,
+   gap: Gap,
+}
+
+impl SecureText {
+   pub fn new() -> Result<Self, Error> {
+      Self::new_with_capacity(0)
+   }
+
+   /// Creates an empty `SecureText` with room for `capacity` bytes before the first reallocation
+   pub fn new_with_capacity(capacity: usize) -> Result<Self, Error> {
+      let buf = zeroed_buffer(capacity)?;
+      let gap = Gap {
+         start: 0,
+         end: buf.len(),
+         chars_before: 0,
+         char_len: 0,
+      };
+      Ok(SecureText { buf, gap })
+   }
+
+   /// Returns the length of the text in bytes
+   pub fn byte_len(&self) -> usize {
+      self.buf.len() - self.gap.len()
+   }
+
+   /// Returns the number of chars in the text, this is tracked and never rescanned
+   pub fn char_len(&self) -> usize {
+      self.gap.char_len
+   }
+
+   pub fn is_empty(&self) -> bool {
+      self.byte_len() == 0
+   }
+
+   /// Inserts `text` at the given character index
+   ///
+   /// Indices past the end insert at the end.
+   ///
+   /// # Returns
+   ///
+   /// The number of characters inserted
+   ///
+   /// # Panics
+   /// Panics if the buffer needs to grow and the new allocation fails.
+   pub fn insert(&mut self, char_idx: usize, text: &str) -> usize {
+      if text.is_empty() {
+         return 0;
+      }
+
+      if self.gap.len() < text.len() {
+         self.grow(text.len());
+      }
+
+      let gap = &mut self.gap;
+      self.buf.unlock_slice_mut(|bytes| {
+         let byte_idx = gap.byte_idx(bytes, char_idx);
+         gap.move_to(bytes, byte_idx);
+         gap.insert(bytes, text)
+      })
+   }
+
+   /// Deletes the characters in `char_range` and zeroizes them
+   ///
+   /// The range is clamped to the end of the text.
+   pub fn delete(&mut self, char_range: Range<usize>) {
+      if char_range.start >= char_range.end || char_range.start >= self.gap.char_len {
+         return;
+      }
+
+      let char_count = char_range.end.min(self.gap.char_len) - char_range.start;
+      let gap = &mut self.gap;
+      self.buf.unlock_slice_mut(|bytes| {
+         let start = gap.byte_idx(bytes, char_range.start);
+         let end = gap.byte_idx(bytes, char_range.end);
+         gap.move_to(bytes, start);
+         gap.delete_after(bytes, end - start, char_count);
+      });
+   }
+
+   /// Returns the byte index of the given char index
+   ///
+   /// The lookup starts from the gap, so indices near the last edit are cheap.
+   pub fn char_to_byte_idx(&self, char_idx: usize) -> usize {
+      self
+         .buf
+         .unlock_slice(|bytes| self.gap.byte_idx(bytes, char_idx))
+   }
+
+   /// Zeroizes the whole buffer and empties the text, the allocation is kept
+   pub fn erase(&mut self) {
+      self.buf.unlock_slice_mut(|bytes| bytes.zeroize());
+      self.gap = Gap {
+         start: 0,
+         end: self.buf.len(),
+         chars_before: 0,
+         char_len: 0,
+      };
+   }
+
+   /// Immutable access to the text as the two `&str` on either side of the gap
+   ///
+   /// Unlike [`unlock_str`](Self::unlock_str) this never moves any bytes.
+   pub fn unlock_parts<F, R>(&self, f: F) -> R
+   where
+      F: FnOnce(&str, &str) -> R,
+   {
+      self.buf.unlock_slice(|bytes| {
+         // The gap always sits on a char boundary
+         let before = unsafe { core::str::from_utf8_unchecked(&bytes[..self.gap.start]) };
+         let after = unsafe { core::str::from_utf8_unchecked(&bytes[self.gap.end..]) };
+         f(before, after)
+      })
+   }
+
+   /// Immutable access to the whole text as `&str`
+   ///
+   /// The gap has to be moved to the end to make the text contiguous,
+   /// which costs O(distance) just like moving the cursor there.
+   pub fn unlock_str<F, R>(&mut self, f: F) -> R
+   where
+      F: FnOnce(&str) -> R,
+   {
+      let gap = &mut self.gap;
+      self.buf.unlock_slice_mut(|bytes| {
+         gap.move_to(bytes, bytes.len() - gap.len());
+         let str = unsafe { core::str::from_utf8_unchecked(&bytes[..gap.start]) };
+         f(str)
+      })
+   }
+
+   /// Converts the text into a `SecureString` by reusing its allocation
+   pub fn into_secure_string(mut self) -> SecureString {
+      self.unlock_str(|_| ());
+      let mut buf = self.buf;
+      // Everything past the text is the zeroized gap
+      buf.len = self.gap.start;
+      unsafe { SecureString::from_utf8_unchecked(buf) }
+   }
+
+   /// Reallocates so that the gap can hold at least `additional` bytes
+   ///
+   /// The capacity at least doubles, which keeps repeated inserts amortized O(1).
+   fn grow(&mut self, additional: usize) {
+      let text_len = self.byte_len();
+      let required = text_len
+         .checked_add(additional)
+         .expect("SecureText capacity overflow");
+      let new_capacity = required.max(self.buf.len().saturating_mul(2));
+
+      let mut new_buf =
+         zeroed_buffer(new_capacity).expect("Failed to allocate SecureText during grow");
+      let after_len = self.buf.len() - self.gap.end;
+      let new_gap_end = new_buf.len() - after_len;
+
+      self.buf.unlock_slice(|old| {
+         new_buf.unlock_slice_mut(|new| {
+            new[..self.gap.start].copy_from_slice(&old[..self.gap.start]);
+            new[new_gap_end..].copy_from_slice(&old[self.gap.end..]);
+         });
+      });
+
+      // The old buffer is zeroized when it is dropped here
+      self.buf = new_buf;
+      self.gap.end = new_gap_end;
+   }
+}
+
+impl From<SecureString> for SecureText {
+   /// Converts a `SecureString` into a `SecureText` by reusing its allocation
+   ///
+   /// The spare capacity of the string becomes the gap, placed at the end.
+   fn from(string: SecureString) -> Self {
+      let char_len = string.char_len();
+      let mut buf = string.into_bytes();
+      let text_len = buf.len();
+      buf.append_from_iter(core::iter::repeat_n(0u8, buf.capacity - text_len));
+
+      let gap = Gap {
+         start: text_len,
+         end: buf.len(),
+         chars_before: char_len,
+         char_len,
+      };
+      SecureText { buf, gap }
+   }
+}
+
+/// Allocates a `SecureVec<u8>` of `capacity` zero bytes, with `len == capacity`
+fn zeroed_buffer(capacity: usize) -> Result<SecureVec<u8>, Error> {
+   let mut buf = SecureVec::new_with_capacity(capacity)?;
+   buf.append_from_iter(core::iter::repeat_n(0u8, buf.capacity));
+   Ok(buf)
+}
+
+/// Counts the chars in a run of whole UTF-8 characters
+fn count_chars(bytes: &[u8]) -> usize {
+   bytes.iter().filter(|&&b| is_char_start(b)).count()
+}
+
+fn is_char_start(byte: u8) -> bool {
+   (byte & 0xC0) != 0x80
+}
+
+/// Gap bookkeeping, the methods work on the unlocked buffer.
+///
+/// Byte indices given to and returned by the methods are logical (gap excluded).
+struct Gap {
+   start: usize,
+   end: usize,
+   /// Number of chars in `[0, start)`, this is the char to byte index cache
+   chars_before: usize,
+   char_len: usize,
+}
+
+impl Gap {
+   fn len(&self) -> usize {
+      self.end - self.start
+   }
+
+   /// Resolves a char index to a logical byte index by scanning outward from the gap
+   fn byte_idx(&self, bytes: &[u8], char_idx: usize) -> usize {
+      let char_idx = char_idx.min(self.char_len);
+
+      if char_idx <= self.chars_before {
+         let mut remaining = self.chars_before - char_idx;
+         let mut idx = self.start;
+         while remaining > 0 {
+            idx -= 1;
+            if is_char_start(bytes[idx]) {
+               remaining -= 1;
+            }
+         }
+         idx
+      } else {
+         let mut remaining = char_idx - self.chars_before;
+         let mut idx = self.end;
+         while remaining > 0 {
+            idx += 1;
+            while idx < bytes.len() && !is_char_start(bytes[idx]) {
+               idx += 1;
+            }
+            remaining -= 1;
+         }
+         idx - self.len()
+      }
+   }
+
+   /// Moves the gap so that it starts at the logical byte index `byte_idx`
+   ///
+   /// Only the moved bytes are touched, and the ones left behind in the gap are zeroized.
+   fn move_to(&mut self, bytes: &mut [u8], byte_idx: usize) {
+      if byte_idx < self.start {
+         let count = self.start - byte_idx;
+         bytes.copy_within(byte_idx..self.start, self.end - count);
+         self.chars_before -= count_chars(&bytes[self.end - count..self.end]);
+
+         let stale_end = self.start.min(self.end - count);
+         bytes[byte_idx..stale_end].zeroize();
+
+         self.start = byte_idx;
+         self.end -= count;
+      } else if byte_idx > self.start {
+         let count = byte_idx - self.start;
+         bytes.copy_within(self.end..self.end + count, self.start);
+         self.chars_before += count_chars(&bytes[self.start..self.start + count]);
+
+         let stale_start = self.end.max(self.start + count);
+         bytes[stale_start..self.end + count].zeroize();
+
+         self.start += count;
+         self.end += count;
+      }
+   }
+
+   /// Writes `text` at the start of the gap, which must be large enough
+   fn insert(&mut self, bytes: &mut [u8], text: &str) -> usize {
+      bytes[self.start..self.start + text.len()].copy_from_slice(text.as_bytes());
+      self.start += text.len();
+
+      let chars = text.chars().count();
+      self.chars_before += chars;
+      self.char_len += chars;
+      chars
+   }
+
+   /// Widens the gap over the `byte_count` bytes (`char_count` chars) right after it
+   fn delete_after(&mut self, bytes: &mut [u8], byte_count: usize, char_count: usize) {
+      bytes[self.end..self.end + byte_count].zeroize();
+      self.end += byte_count;
+      self.char_len -= char_count;
+   }
+}
+
+#[cfg(all(test, feature = "use_os"))]
+mod tests {
+   use super::*;
+
+   fn text_of(text: &SecureText) -> String {
+      text.unlock_parts(|before, after| format!("{before}{after}"))
+   }
+
+   fn assert_gap_is_zeroized(text: &SecureText) {
+      text.buf.unlock_slice(|bytes| {
+         assert!(bytes[text.gap.start..text.gap.end].iter().all(|&b| b == 0));
+      });
+   }
+
+   #[test]
+   fn test_insert_and_delete() {
+      let mut text = SecureText::new().unwrap();
+      assert!(text.is_empty());
+
+      assert_eq!(text.insert(0, "world"), 5);
+      assert_eq!(text.insert(0, "héllo "), 6);
+      assert_eq!(text.insert(100, " 🦀"), 2);
+      assert_eq!(text_of(&text), "héllo world 🦀");
+      assert_eq!(text.char_len(), 13);
+      assert_eq!(text.byte_len(), "héllo world 🦀".len());
+
+      text.delete(1..2);
+      assert_eq!(text_of(&text), "hllo world 🦀");
+      text.delete(10..100);
+      assert_eq!(text_of(&text), "hllo world");
+      text.delete(3..3);
+      text.delete(50..60);
+      assert_eq!(text_of(&text), "hllo world");
+      assert_eq!(text.char_len(), 10);
+      assert_gap_is_zeroized(&text);
+
+      text.unlock_str(|str| assert_eq!(str, "hllo world"));
+      assert_eq!(text.char_to_byte_idx(4), 4);
+   }
+
+   #[test]
+   fn test_char_to_byte_idx() {
+      let mut text = SecureText::from(SecureString::from("aé🦀b"));
+      text.insert(2, "x");
+      // "aéx🦀b" with the gap after "x"
+      assert_eq!(text.char_to_byte_idx(0), 0);
+      assert_eq!(text.char_to_byte_idx(1), 1);
+      assert_eq!(text.char_to_byte_idx(2), 3);
+      assert_eq!(text.char_to_byte_idx(3), 4);
+      assert_eq!(text.char_to_byte_idx(4), 8);
+      assert_eq!(text.char_to_byte_idx(5), 9);
+      assert_eq!(text.char_to_byte_idx(6), 9);
+   }
+
+   #[test]
+   fn test_matches_string_model() {
+      let mut text = SecureText::new_with_capacity(4).unwrap();
+      let mut model = String::new();
+      let words = ["a", "é", "🦀", "xyz", "\n", "ß€"];
+
+      // Small deterministic LCG so the edits hop around the buffer
+      let mut seed: u64 = 0x5EC0_0000;
+      let mut next = |bound: usize| {
+         seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+         (seed >> 33) as usize % bound.max(1)
+      };
+
+      for _ in 0..500 {
+         let model_chars = model.chars().count();
+         if next(3) == 0 && model_chars > 0 {
+            let start = next(model_chars);
+            let end = start + next(4);
+            text.delete(start..end);
+
+            let byte_start = model
+               .char_indices()
+               .nth(start)
+               .map_or(model.len(), |(i, _)| i);
+            let byte_end = model
+               .char_indices()
+               .nth(end)
+               .map_or(model.len(), |(i, _)| i);
+            model.replace_range(byte_start..byte_end, "");
+         } else {
+            let idx = next(model_chars + 1);
+            let word = words[next(words.len())];
+            text.insert(idx, word);
+
+            let byte_idx = model
+               .char_indices()
+               .nth(idx)
+               .map_or(model.len(), |(i, _)| i);
+            model.insert_str(byte_idx, word);
+         }
+
+         assert_eq!(text.char_len(), model.chars().count());
+      }
+
+      assert_eq!(text_of(&text), model);
+      assert_gap_is_zeroized(&text);
+
+      let string = text.into_secure_string();
+      string.unlock_str(|str| assert_eq!(str, model));
+   }
+
+   #[test]
+   fn test_erase() {
+      let mut text = SecureText::from(SecureString::from("secret"));
+      text.insert(3, "_");
+      text.erase();
+      assert!(text.is_empty());
+      assert_eq!(text.char_len(), 0);
+      assert_gap_is_zeroized(&text);
+
+      text.insert(0, "new");
+      assert_eq!(text_of(&text), "new");
+   }
+
+   #[test]
+   fn test_into_secure_string_reuses_allocation() {
+      let mut text = SecureText::new_with_capacity(64).unwrap();
+      text.insert(0, "pem");
+      let string = text.into_secure_string();
+      assert_eq!(string.byte_len(), 3);
+      string.unlock_str(|str| assert_eq!(str, "pem"));
+
+      let empty = SecureText::new().unwrap().into_secure_string();
+      assert!(empty.is_empty());
+   }
+}
@@ -0,0 +1,296 @@
+use super::SecureString;
+use core::{any::TypeId, ops::Range};
+use egui::TextBuffer;
+
+/// A [SecureString] unlocked for editing by an `egui::TextEdit`.
+///
+/// It is created by [SecureString::unlock_text_buffer] and only lives for the closure,
+/// the memory is locked again as soon as it is dropped.
+///
+/// `as_str` and `char_range` borrow straight from the secure memory and every edit
+/// is done in place, so the buffer itself never copies the text.
+/// `take` erases the text but returns an empty `String` instead of a copy of it.
+///
+/// # Notes
+///
+/// `egui::TextEdit` keeps copies of the text for its undo history and change detection,
+/// these copies are outside of the control of this crate.
+///
+/// Always use `.password(true)` so the text is masked before it is laid out and
+/// copying it to the clipboard is disabled.
+pub struct SecureTextBuffer<'a> {
+   string: &'a mut SecureString,
+}
+
+impl<'a> SecureTextBuffer<'a> {
+   fn new(string: &'a mut SecureString) -> Self {
+      let ok = string.unlock_memory();
+      debug_assert!(ok, "SecureTextBuffer::new: unlock_memory failed");
+      SecureTextBuffer { string }
+   }
+
+   /// Every `SecureString` mutation locks the memory when it is done
+   /// (and may move it to a new allocation), so unlock it again.
+   fn unlock_after_edit(&mut self) {
+      let ok = self.string.unlock_memory();
+      debug_assert!(
+         ok,
+         "SecureTextBuffer::unlock_after_edit: unlock_memory failed"
+      );
+   }
+}
+
+impl Drop for SecureTextBuffer<'_> {
+   fn drop(&mut self) {
+      let ok = self.string.lock_memory();
+      debug_assert!(ok, "SecureTextBuffer::drop: lock_memory failed");
+   }
+}
+
+impl TextBuffer for SecureTextBuffer<'_> {
+   fn is_mutable(&self) -> bool {
+      true
+   }
+
+   fn as_str(&self) -> &str {
+      // The memory stays unlocked for as long as `self` is alive
+      unsafe { self.string.as_str_unlocked() }
+   }
+
+   fn insert_text(&mut self, text: &str, char_index: usize) -> usize {
+      // A bounded string takes as many whole chars as still fit instead of panicking
+      let text = if self.string.is_bounded() {
+         let available = self.string.capacity() - self.string.byte_len();
+         let mut end = text.len().min(available);
+         while !text.is_char_boundary(end) {
+            end -= 1;
+         }
+         &text[..end]
+      } else {
+         text
+      };
+
+      let inserted = self.string.insert_text_at_char_idx(char_index, text);
+      self.unlock_after_edit();
+      inserted
+   }
+
+   fn delete_char_range(&mut self, char_range: Range<usize>) {
+      self.string.delete_text_char_range(char_range);
+      self.unlock_after_edit();
+   }
+
+   fn clear(&mut self) {
+      self.string.erase();
+      self.unlock_after_edit();
+   }
+
+   /// Erases the text and returns an empty `String`
+   ///
+   /// The default implementation would copy the secret into a plain `String`.
+   fn take(&mut self) -> String {
+      self.clear();
+      String::new()
+   }
+
+   fn type_id(&self) -> TypeId {
+      TypeId::of::<SecureTextBuffer<'static>>()
+   }
+}
+
+impl SecureString {
+   /// Mutable access as an `egui::TextBuffer`
+   ///
+   /// The memory is unlocked for the duration of the closure, so show the text edit widget inside it.
+   ///
+   /// # Example
+   ///
+   /// ```no_run
+   /// use secure_types::SecureString;
+   ///
+   /// fn password_field(ui: &mut egui::Ui, password: &mut SecureString) -> egui::Response {
+   ///     password.unlock_text_buffer(|buffer| {
+   ///         ui.add(egui::TextEdit::singleline(buffer).password(true))
+   ///     })
+   /// }
+   /// ```
+   pub fn unlock_text_buffer<F, R>(&mut self, f: F) -> R
+   where
+      F: FnOnce(&mut SecureTextBuffer<'_>) -> R,
+   {
+      let mut buffer = SecureTextBuffer::new(self);
+      f(&mut buffer)
+   }
+}
+
+#[cfg(all(test, feature = "use_os"))]
+mod tests {
+   use super::*;
+   use egui::{CentralPanel, Context, Event, Id, Key, Modifiers, RawInput, TextEdit};
+   use std::process::{Command, Stdio};
+
+   fn key(key: Key) -> Event {
+      Event::Key {
+         key,
+         physical_key: None,
+         pressed: true,
+         repeat: false,
+         modifiers: Modifiers::NONE,
+      }
+   }
+
+   fn run_frame(ctx: &Context, secret: &mut SecureString, events: Vec<Event>) {
+      let input = RawInput {
+         events,
+         ..Default::default()
+      };
+
+      let _output = ctx.run(input, |ctx| {
+         CentralPanel::default().show(ctx, |ui| {
+            secret.unlock_text_buffer(|buffer| {
+               let edit = TextEdit::singleline(buffer)
+                  .id(Id::new("password"))
+                  .password(true);
+               ui.add(edit).request_focus();
+            });
+         });
+      });
+   }
+
+   #[test]
+   fn test_text_edit_typing() {
+      let ctx = Context::default();
+      let mut secret = SecureString::new().unwrap();
+
+      // The first frame only gives the text edit focus
+      run_frame(&ctx, &mut secret, vec![]);
+
+      run_frame(
+         &ctx,
+         &mut secret,
+         vec![Event::Text("hunter🦀".into())],
+      );
+      secret.unlock_str(|str| assert_eq!(str, "hunter🦀"));
+
+      run_frame(
+         &ctx,
+         &mut secret,
+         vec![key(Key::Backspace), Event::Text("2".into())],
+      );
+      secret.unlock_str(|str| assert_eq!(str, "hunter2"));
+
+      run_frame(
+         &ctx,
+         &mut secret,
+         vec![key(Key::Home), key(Key::Delete), Event::Text("H".into())],
+      );
+      secret.unlock_str(|str| assert_eq!(str, "Hunter2"));
+   }
+
+   #[test]
+   fn test_text_buffer_methods() {
+      let mut secret = SecureString::from("password");
+
+      secret.unlock_text_buffer(|buffer| {
+         assert_eq!(buffer.as_str(), "password");
+         assert_eq!(buffer.char_range(0..4), "pass");
+
+         // Forces the string to grow into a new allocation
+         assert_eq!(buffer.insert_text(" is long enough now", 8), 19);
+         assert_eq!(buffer.as_str(), "password is long enough now");
+
+         buffer.delete_char_range(8..27);
+         buffer.replace_with("hunter2");
+         assert_eq!(buffer.as_str(), "hunter2");
+
+         buffer.clear();
+         assert_eq!(buffer.as_str(), "");
+
+         buffer.insert_text("hunter2", 0);
+         assert_eq!(buffer.take(), "");
+         assert_eq!(buffer.as_str(), "");
+      });
+
+      assert!(secret.is_empty());
+   }
+
+   #[test]
+   fn test_memory_is_locked_after_text_buffer() {
+      let arg = "CRASH_TEST_SECURETEXTBUFFER_LOCKED";
+
+      if std::env::args().any(|a| a == arg) {
+         let mut secret = SecureString::from("password");
+         secret.unlock_text_buffer(|buffer| {
+            buffer.insert_text(" that grows into a new allocation", 8);
+         });
+
+         // Only builds the `&str`, nothing is read until we dereference it.
+         let ptr = unsafe { secret.as_str_unlocked().as_ptr() };
+         let _value = unsafe { core::hint::black_box(*ptr) };
+
+         std::process::exit(1);
+      }
+
+      let child = Command::new(std::env::current_exe().unwrap())
+         .arg("text_buffer::tests::test_memory_is_locked_after_text_buffer")
+         .arg(arg)
+         .arg("--nocapture")
+         .stdout(Stdio::piped())
+         .stderr(Stdio::piped())
+         .spawn()
+         .expect("Failed to spawn child process");
+
+      let output = child.wait_with_output().expect("Failed to wait on child");
+      let status = output.status;
+
+      assert!(
+         !status.success(),
+         "Process exited successfully with code {:?}, but it should have crashed.",
+         status.code()
+      );
+
+      #[cfg(unix)]
+      {
+         use std::os::unix::process::ExitStatusExt;
+         let signal = status
+            .signal()
+            .expect("Process was not terminated by a signal on Unix.");
+         assert!(
+            signal == libc::SIGSEGV || signal == libc::SIGBUS,
+            "Process terminated with unexpected signal: {}",
+            signal
+         );
+      }
+
+      #[cfg(windows)]
+      {
+         const STATUS_ACCESS_VIOLATION: i32 = 0xC0000005_u32 as i32;
+         assert_eq!(
+            status.code(),
+            Some(STATUS_ACCESS_VIOLATION),
+            "Process exited with unexpected code: {:x?}. Expected STATUS_ACCESS_VIOLATION.",
+            status.code()
+         );
+      }
+   }
+
+   #[test]
+   fn test_bounded_text_edit() {
+      let ctx = Context::default();
+      let mut secret = SecureString::bounded(8).unwrap();
+
+      run_frame(&ctx, &mut secret, vec![]);
+      run_frame(
+         &ctx,
+         &mut secret,
+         vec![Event::Text("hunter🦀🦀".into())],
+      );
+
+      // Only the whole chars that fit are inserted, and the string never moves
+      secret.unlock_str(|str| assert_eq!(str, "hunter"));
+      assert_eq!(secret.capacity(), 8);
+
+      run_frame(&ctx, &mut secret, vec![Event::Text("12".into())]);
+      secret.unlock_str(|str| assert_eq!(str, "hunter12"));
+   }
+}
@@ -4,10 +4,12 @@
 #[cfg(not(feature = "use_os"))]
 use alloc::alloc::Layout;
 
+#[cfg(not(feature = "use_os"))]
+use alloc::vec::Vec;
 #[cfg(feature = "use_os")]
 use std::vec::Vec;
 
-use super::{Error, SecureArray, alloc};
+use super::{Error, SecureArray, SecureSlice, SecureSliceMut, SecureString, UnlockCount, alloc};
 use core::{
    marker::PhantomData,
    mem,
@@ -31,7 +33,7 @@ struct UnlockGuard<'a, T: Zeroize> {
 
 impl<'a, T: Zeroize> UnlockGuard<'a, T> {
    fn new(vec: &'a SecureVec<T>) -> Self {
-      let ok = vec.unlock_memory();
+      let ok = vec.unlocks.acquire(|| vec.unlock_memory());
       debug_assert!(ok, "UnlockGuard::new: unlock_memory failed");
 
       UnlockGuard { vec }
@@ -40,7 +42,7 @@ impl<'a, T: Zeroize> UnlockGuard<'a, T> {
 
 impl<'a, T: Zeroize> Drop for UnlockGuard<'a, T> {
    fn drop(&mut self) {
-      let ok = self.vec.lock_memory();
+      let ok = self.vec.unlocks.release(|| self.vec.lock_memory());
       debug_assert!(ok, "UnlockGuard::drop: lock_memory failed");
    }
 }
@@ -111,6 +113,8 @@ where
    ptr: NonNull<T>,
    pub(crate) len: usize,
    pub(crate) capacity: usize,
+   fixed_capacity: bool,
+   unlocks: UnlockCount,
    _marker: PhantomData<T>,
 }
 
@@ -128,6 +132,8 @@ impl<T: Zeroize> SecureVec<T> {
          ptr,
          len: 0,
          capacity,
+         fixed_capacity: false,
+         unlocks: UnlockCount::default(),
          _marker: PhantomData,
       };
 
@@ -158,6 +164,8 @@ impl<T: Zeroize> SecureVec<T> {
          ptr,
          len: 0,
          capacity,
+         fixed_capacity: false,
+         unlocks: UnlockCount::default(),
          _marker: PhantomData,
       };
 
@@ -171,6 +179,38 @@ impl<T: Zeroize> SecureVec<T> {
       Ok(secure)
    }
 
+   /// Create a new `SecureVec` that never reallocates
+   ///
+   /// The memory address stays the same for the whole lifetime of the vector,
+   /// and growing past `capacity` fails with [Error::CapacityExceeded]
+   /// from the `try_*` methods, or panics from the infallible ones like [`push`](Self::push).
+   ///
+   /// # Errors
+   /// Returns [Error::LengthCannotBeZero] if `capacity` is zero.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::{Error, SecureBytes};
+   ///
+   /// let mut pin = SecureBytes::with_fixed_capacity(4).unwrap();
+   /// for digit in [1, 2, 3, 4] {
+   ///     pin.try_push(digit).unwrap();
+   /// }
+   ///
+   /// assert!(matches!(pin.try_push(5), Err(Error::CapacityExceeded)));
+   /// assert_eq!(pin.len(), 4);
+   /// ```
+   pub fn with_fixed_capacity(capacity: usize) -> Result<Self, Error> {
+      if capacity == 0 {
+         return Err(Error::LengthCannotBeZero);
+      }
+
+      let mut secure = Self::new_with_capacity(capacity)?;
+      secure.fixed_capacity = true;
+      Ok(secure)
+   }
+
    #[cfg(feature = "use_os")]
    /// Create a new `SecureVec` from a `Vec`
    ///
@@ -235,6 +275,8 @@ impl<T: Zeroize> SecureVec<T> {
          ptr,
          len,
          capacity,
+         fixed_capacity: false,
+         unlocks: UnlockCount::default(),
          _marker: PhantomData,
       };
 
@@ -288,6 +330,16 @@ impl<T: Zeroize> SecureVec<T> {
       self.len() == 0
    }
 
+   /// Returns the number of elements the vector can hold without reallocating
+   pub fn capacity(&self) -> usize {
+      self.capacity
+   }
+
+   /// Returns `true` if the vector was created with [`with_fixed_capacity`](Self::with_fixed_capacity)
+   pub fn is_fixed_capacity(&self) -> bool {
+      self.fixed_capacity
+   }
+
    /// Returns the pointer to the locked memory region
    ///
    /// # DANGER
@@ -310,6 +362,50 @@ impl<T: Zeroize> SecureVec<T> {
       self.capacity * mem::size_of::<T>()
    }
 
+   /// Takes ownership of an existing secure allocation.
+   ///
+   /// # Safety
+   /// `ptr` must come from `alloc::<T>(capacity * size_of::<T>())`, be locked,
+   /// and hold `len` initialized elements.
+   pub(crate) unsafe fn from_raw_parts(ptr: NonNull<T>, len: usize, capacity: usize) -> Self {
+      SecureVec {
+         ptr,
+         len,
+         capacity,
+         fixed_capacity: false,
+         unlocks: UnlockCount::default(),
+         _marker: PhantomData,
+      }
+   }
+
+   /// Releases ownership of the allocation as `(ptr, len, capacity)`
+   /// without zeroizing or freeing it.
+   pub(crate) fn into_raw_parts(self) -> (NonNull<T>, usize, usize) {
+      let mut vec = mem::ManuallyDrop::new(self);
+      unsafe { ptr::drop_in_place(&mut vec.unlocks) };
+      (vec.ptr, vec.len, vec.capacity)
+   }
+
+   /// Bitwise moves every element to `dst`, wipes the source slots and clears the vector.
+   ///
+   /// The vector unlocks its own memory, the caller is responsible for `dst`.
+   ///
+   /// # Safety
+   /// `dst` must be writable for `self.len()` elements and must not overlap this vector.
+   pub(crate) unsafe fn move_all_to(&mut self, dst: *mut T) {
+      let ok = self.unlock_memory();
+      debug_assert!(ok, "SecureVec::move_all_to: unlock_memory failed");
+
+      unsafe {
+         ptr::copy_nonoverlapping(self.ptr.as_ptr(), dst, self.len);
+         wipe_slots(self.ptr.as_ptr(), self.len);
+      }
+      self.len = 0;
+
+      let ok = self.lock_memory();
+      debug_assert!(ok, "SecureVec::move_all_to: lock_memory failed");
+   }
+
    pub(crate) fn as_mut_ptr(&mut self) -> *mut u8 {
       self.ptr.as_ptr() as *mut u8
    }
@@ -361,6 +457,15 @@ impl<T: Zeroize> SecureVec<T> {
       result
    }
 
+   /// Returns the elements without unlocking the memory.
+   ///
+   /// # Safety
+   /// The memory must already be unlocked and stay unlocked while the slice is alive.
+   #[cfg(feature = "egui")]
+   pub(crate) unsafe fn as_slice_unlocked(&self) -> &[T] {
+      unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+   }
+
    /// Immutable access to the `SecureVec` as `&[T]`
    pub fn unlock_slice<F, R>(&self, f: F) -> R
    where
@@ -412,6 +517,129 @@ impl<T: Zeroize> SecureVec<T> {
       }
    }
 
+   /// Immutable access to the `SecureVec` as `&[[T; N]]`
+   ///
+   /// Just like `slice::chunks_exact`, if the length is not a multiple of `N`
+   /// the remaining elements at the end are not included.
+   ///
+   /// `N` is inferred from the closure's argument type, or can be given as `::<N, _>`.
+   /// A zero `N` is a compile error.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::SecureBytes;
+   ///
+   /// let master = SecureBytes::from_slice(&[1u8; 64]).unwrap();
+   /// master.unlock_chunks_exact(|subkeys: &[[u8; 32]]| {
+   ///     assert_eq!(subkeys.len(), 2);
+   ///     assert_eq!(subkeys[1], [1u8; 32]);
+   /// });
+   ///
+   /// let count = master.unlock_chunks_exact::<16, _>(|chunks| chunks.len());
+   /// assert_eq!(count, 4);
+   /// ```
+   pub fn unlock_chunks_exact<const N: usize, R>(&self, f: impl FnOnce(&[[T; N]]) -> R) -> R {
+      const { assert!(N != 0, "chunk size must be non-zero") };
+
+      let _guard = UnlockGuard::new(self);
+      // SAFETY: `[T; N]` has the same layout as `N` consecutive `T`s.
+      let chunks =
+         unsafe { core::slice::from_raw_parts(self.ptr.as_ptr() as *const [T; N], self.len / N) };
+      f(chunks)
+   }
+
+   /// Splits the vector into `SecureArray`s of exactly `N` elements.
+   ///
+   /// The elements are cloned straight from this vector's memory into each array,
+   /// this vector is left unchanged. A zero `N` is a compile error.
+   ///
+   /// # Errors
+   /// Returns [Error::LengthMismatch] if the length is not a multiple of `N`.
+   pub fn split_into_arrays<const N: usize>(&self) -> Result<Vec<SecureArray<T, N>>, Error>
+   where
+      T: Clone,
+   {
+      const { assert!(N != 0, "chunk size must be non-zero") };
+
+      if !self.len.is_multiple_of(N) {
+         return Err(Error::LengthMismatch);
+      }
+
+      // Allocate everything up front so no allocation happens while this vector is unlocked.
+      let count = self.len / N;
+      let mut uninit = Vec::with_capacity(count);
+      for _ in 0..count {
+         uninit.push(SecureArray::empty_uninit()?);
+      }
+      let mut arrays = Vec::with_capacity(count);
+
+      self.unlock_slice(|slice| {
+         for (array, chunk) in uninit.into_iter().zip(slice.chunks_exact(N)) {
+            arrays.push(array.init_from_clone(chunk));
+         }
+      });
+
+      Ok(arrays)
+   }
+
+   /// Creates a new `SecureVec` by concatenating the given arrays.
+   ///
+   /// The elements are cloned straight from each array's memory into the new vector.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::{SecureArray, SecureVec};
+   ///
+   /// let a: SecureArray<u8, 2> = SecureArray::from_slice(&[1, 2]).unwrap();
+   /// let b: SecureArray<u8, 2> = SecureArray::from_slice(&[3, 4]).unwrap();
+   /// let joined = SecureVec::concat(&[&a, &b]).unwrap();
+   ///
+   /// joined.unlock_slice(|slice| assert_eq!(slice, &[1, 2, 3, 4]));
+   /// ```
+   pub fn concat<const N: usize>(arrays: &[&SecureArray<T, N>]) -> Result<Self, Error>
+   where
+      T: Clone,
+   {
+      let capacity = arrays.len().checked_mul(N).ok_or(Error::AllocationFailed)?;
+      let mut new_vec = SecureVec::new_with_capacity(capacity)?;
+
+      for array in arrays {
+         array.unlock(|slice| new_vec.append_clones(slice));
+      }
+
+      Ok(new_vec)
+   }
+
+   /// Returns a read-only view of the elements in `range` without copying them
+   ///
+   /// The view shares this vector's lock state, see [SecureSlice].
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds.
+   pub fn slice<R>(&self, range: R) -> SecureSlice<'_, T>
+   where
+      R: RangeBounds<usize>,
+   {
+      let (start, end) = resolve_range_indices(range, self.len);
+      unsafe { SecureSlice::new(self.ptr, &self.unlocks, start, end - start) }
+   }
+
+   /// Returns a mutable view of the elements in `range` without copying them
+   ///
+   /// The view shares this vector's lock state, see [SecureSliceMut].
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds.
+   pub fn slice_mut<R>(&mut self, range: R) -> SecureSliceMut<'_, T>
+   where
+      R: RangeBounds<usize>,
+   {
+      let (start, end) = resolve_range_indices(range, self.len);
+      unsafe { SecureSliceMut::new(self.ptr, &self.unlocks, start, end - start) }
+   }
+
    /// Erase the underlying data and clears the vector
    ///
    /// The memory is locked again and the capacity is preserved for reuse
@@ -442,9 +670,31 @@ impl<T: Zeroize> SecureVec<T> {
       self.len = 0;
    }
 
+   /// Appends an element to the back of the vector
+   ///
+   /// # Panics
+   /// Panics if the vector has a fixed capacity that is already full, or if the allocation fails.
    pub fn push(&mut self, value: T) {
       self.reserve(1);
+      self.push_reserved(value);
+   }
 
+   /// Appends an element to the back of the vector without panicking
+   ///
+   /// # Errors
+   /// Returns [Error::CapacityExceeded] if the vector has a fixed capacity that is already full,
+   /// or an allocation error if it cannot grow. In both cases `value` is zeroized and dropped.
+   pub fn try_push(&mut self, mut value: T) -> Result<(), Error> {
+      if let Err(e) = self.try_reserve(1) {
+         value.zeroize();
+         return Err(e);
+      }
+      self.push_reserved(value);
+      Ok(())
+   }
+
+   /// Writes `value` after the last element, the capacity must already be reserved
+   fn push_reserved(&mut self, value: T) {
       let ok = self.unlock_memory();
       debug_assert!(ok, "SecureVec::push: unlock_memory failed");
 
@@ -465,28 +715,55 @@ impl<T: Zeroize> SecureVec<T> {
    ///
    /// # Panics
    ///
-   /// Panics if the new capacity overflows `usize` or if the allocation fails.
+   /// Panics if the new capacity overflows `usize`, if the allocation fails
+   /// or if the vector has a fixed capacity that is too small.
    pub fn reserve(&mut self, additional: usize) {
-      if self.len() + additional <= self.capacity {
-         return;
+      match self.try_reserve(additional) {
+         Ok(()) => {}
+         Err(Error::CapacityExceeded) => panic!(
+            "SecureVec::reserve: fixed capacity of {} exceeded",
+            self.capacity
+         ),
+         // Safe to panic here because the memory is locked
+         Err(_) => panic!(
+            "secure-types: failed to allocate locked memory \
+          (possibly RLIMIT_MEMLOCK exhausted); SecureVec left unchanged"
+         ),
+      }
+   }
+
+   /// Ensures that the vector has enough capacity for at least `additional` more elements without panicking.
+   ///
+   /// # Errors
+   /// Returns [Error::CapacityExceeded] if the vector has a fixed capacity that is too small,
+   /// or an allocation error if the new capacity overflows or the allocation fails.
+   /// The vector is left unchanged.
+   pub fn try_reserve(&mut self, additional: usize) -> Result<(), Error> {
+      let required_capacity = self
+         .len()
+         .checked_add(additional)
+         .ok_or(Error::AllocationFailed)?;
+
+      if required_capacity <= self.capacity {
+         return Ok(());
+      }
+
+      if self.fixed_capacity {
+         return Err(Error::CapacityExceeded);
       }
 
       // Use an amortized growth strategy to avoid reallocating on every push
-      let required_capacity = self.len() + additional;
-      let new_capacity = (self.capacity.max(1) * 2).max(required_capacity);
-
-      let new_size = new_capacity * mem::size_of::<T>();
-
-      // Safe to panic here because the memory is locked
-      let new_ptr = unsafe {
-         alloc::<T>(new_size).unwrap_or_else(|_| {
-            panic!(
-               "secure-types: failed to allocate {} bytes of locked memory \
-          (possibly RLIMIT_MEMLOCK exhausted); SecureVec left unchanged",
-               new_size
-            )
-         })
-      };
+      let new_capacity = self
+         .capacity
+         .max(1)
+         .saturating_mul(2)
+         .max(required_capacity);
+
+      let new_size = new_capacity
+         .checked_mul(mem::size_of::<T>())
+         .ok_or(Error::AllocationFailed)?;
+
+      let new_ptr = unsafe { alloc::<T>(new_size)? };
 
       // Copy data to new pointer
       unsafe {
@@ -524,13 +801,18 @@ impl<T: Zeroize> SecureVec<T> {
       self.capacity = new_capacity;
       let ok = self.lock_memory();
       debug_assert!(ok, "SecureVec::reserve: lock_memory failed");
+      Ok(())
    }
 
    /// Creates a draining iterator that removes the specified range from the vector
    /// and yields the removed items.
    ///
-   /// Note: The vector is unlocked during the lifetime of the `Drain` iterator.
-   /// The memory is relocked when the `Drain` iterator is dropped.
+   /// The vector is never left unlocked between calls to `next()`: every item is moved
+   /// out under its own short unlock, and its slot is wiped before the memory is locked again.
+   /// The tail is shifted into place when the `Drain` iterator is dropped.
+   ///
+   /// If the `Drain` is leaked (eg. via `mem::forget`) the memory stays locked,
+   /// the vector is truncated to the start of the range and the remaining elements are leaked.
    ///
    /// # Panics
    /// Panics if the starting point is greater than the end point or if the end point
@@ -545,11 +827,10 @@ impl<T: Zeroize> SecureVec<T> {
 
       let tail_len = original_len - drain_end_idx;
 
+      // Shrink the length up front so a leaked `Drain` can only leak elements,
+      // it can never leave moved-out slots visible as initialized values.
       self.len = drain_start_idx;
 
-      let ok = self.unlock_memory();
-      debug_assert!(ok, "SecureVec::drain: unlock_memory failed");
-
       Drain {
          vec_ref: self,
          drain_start_index: drain_start_idx,
@@ -561,43 +842,195 @@ impl<T: Zeroize> SecureVec<T> {
       }
    }
 
-   /// Initializes a freshly-allocated (uninitialized) buffer by cloning `src`
-   /// into it. Uses `ptr::write` so the uninitialized destination slots are
-   /// never read, never dropped, and no `&mut [T]` is ever formed over them.
+   /// Moves the elements in `range` to the end of `dst`.
    ///
-   /// `len` is set only after every write succeeds, so a panic from
-   /// `T::clone` leaves the vector at its previous length (0 for a fresh one).
-   pub(crate) fn init_from_clone(&mut self, src: &[T])
+   /// The elements are copied directly from this vector's unlocked memory into `dst`'s unlocked
+   /// memory, so they never pass through the stack. The source slots are wiped afterwards
+   /// and the tail is shifted down to close the gap.
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds or if `dst` fails to grow.
+   ///
+   /// # Example
+   ///
+   /// ```
+   /// use secure_types::SecureVec;
+   ///
+   /// let mut src = SecureVec::from_slice(&[1u8, 2, 3, 4]).unwrap();
+   /// let mut dst = SecureVec::new().unwrap();
+   /// src.drain_into(1..3, &mut dst);
+   ///
+   /// src.unlock_slice(|slice| assert_eq!(slice, &[1, 4]));
+   /// dst.unlock_slice(|slice| assert_eq!(slice, &[2, 3]));
+   /// ```
+   pub fn drain_into<R>(&mut self, range: R, dst: &mut SecureVec<T>)
    where
+      R: RangeBounds<usize>,
+   {
+      let (start, end) = resolve_range_indices(range, self.len);
+      let count = end - start;
+      if count == 0 {
+         return;
+      }
+
+      // Grow `dst` before anything is unlocked, so a failed allocation exposes nothing.
+      dst.reserve(count);
+      let tail_len = self.len - end;
+
+      let ok = self.unlock_memory();
+      debug_assert!(ok, "SecureVec::drain_into: unlock_memory failed");
+
+      unsafe {
+         let src = self.ptr.as_ptr();
+         dst.append_moved(src.add(start), count);
+         wipe_slots(src.add(start), count);
+
+         if tail_len > 0 {
+            ptr::copy(src.add(end), src.add(start), tail_len);
+            wipe_slots(src.add(start + tail_len), count);
+         }
+      }
+
+      self.len -= count;
+
+      let ok = self.lock_memory();
+      debug_assert!(ok, "SecureVec::drain_into: lock_memory failed");
+   }
+
+   /// Clones the elements in `range` to the end of `dst`.
+   ///
+   /// The clones are written directly into `dst`'s unlocked memory, this vector is left unchanged.
+   ///
+   /// # Panics
+   /// Panics if the range is out of bounds or if `dst` fails to grow.
+   pub fn copy_range_into<R>(&self, range: R, dst: &mut SecureVec<T>)
+   where
+      R: RangeBounds<usize>,
       T: Clone,
    {
-      debug_assert!(src.len() <= self.capacity);
+      let (start, end) = resolve_range_indices(range, self.len);
+      if start == end {
+         return;
+      }
+
+      dst.reserve(end - start);
+      self.unlock_slice(|slice| dst.append_clones(&slice[start..end]));
+   }
+
+   /// Bitwise moves `count` elements from `src` to the end of the vector.
+   ///
+   /// The capacity must already be reserved. The vector unlocks its own memory,
+   /// the caller is responsible for `src`.
+   ///
+   /// # Safety
+   /// `src` must be readable for `count` elements, and the caller must treat them as moved-out.
+   pub(crate) unsafe fn append_moved(&mut self, src: *const T, count: usize) {
+      debug_assert!(self.len + count <= self.capacity);
 
       let ok = self.unlock_memory();
       debug_assert!(
          ok,
-         "SecureVec::init_from_clone: unlock_memory failed"
+         "SecureVec::append_moved: unlock_memory failed"
+      );
+
+      unsafe {
+         ptr::copy_nonoverlapping(src, self.ptr.as_ptr().add(self.len), count);
+      }
+      self.len += count;
+
+      let ok = self.lock_memory();
+      debug_assert!(ok, "SecureVec::append_moved: lock_memory failed");
+   }
+
+   /// Clones `src` to the end of the vector. The capacity must already be reserved.
+   ///
+   /// `len` grows one element at a time, so a panic from `T::clone` never
+   /// leaves uninitialized slots inside the vector.
+   pub(crate) fn append_clones(&mut self, src: &[T])
+   where
+      T: Clone,
+   {
+      debug_assert!(self.len + src.len() <= self.capacity);
+
+      let ok = self.unlock_memory();
+      debug_assert!(
+         ok,
+         "SecureVec::append_clones: unlock_memory failed"
       );
 
       unsafe {
          let dst = self.ptr.as_ptr();
-         for (i, item) in src.iter().enumerate() {
-            core::ptr::write(dst.add(i), item.clone());
+         for item in src {
+            core::ptr::write(dst.add(self.len), item.clone());
+            self.len += 1;
+         }
+      }
+
+      let ok = self.lock_memory();
+      debug_assert!(ok, "SecureVec::append_clones: lock_memory failed");
+   }
+
+   /// Writes the items of `iter` to the end of the vector under a single unlock.
+   ///
+   /// The capacity must already be reserved for every item the iterator yields,
+   /// anything past the capacity is never written.
+   pub(crate) fn append_from_iter<I>(&mut self, iter: I)
+   where
+      I: Iterator<Item = T>,
+   {
+      let ok = self.unlock_memory();
+      debug_assert!(
+         ok,
+         "SecureVec::append_from_iter: unlock_memory failed"
+      );
+
+      unsafe {
+         let dst = self.ptr.as_ptr();
+         for item in iter.take(self.capacity - self.len) {
+            core::ptr::write(dst.add(self.len), item);
+            self.len += 1;
          }
       }
 
-      self.len = src.len();
       let ok = self.lock_memory();
       debug_assert!(
          ok,
-         "SecureVec::init_from_clone: lock_memory failed"
+         "SecureVec::append_from_iter: lock_memory failed"
       );
    }
+
+   /// Initializes a freshly-allocated (uninitialized) buffer by cloning `src`
+   /// into it. Uses `ptr::write` so the uninitialized destination slots are
+   /// never read, never dropped, and no `&mut [T]` is ever formed over them.
+   ///
+   /// `len` is set only after every write succeeds, so a panic from
+   /// `T::clone` leaves the vector at its previous length (0 for a fresh one),
+   /// and the clones written so far are zeroized and dropped.
+   pub(crate) fn init_from_clone(&mut self, src: &[T])
+   where
+      T: Clone,
+   {
+      debug_assert!(src.len() <= self.capacity);
+
+      let dst = self.ptr.as_ptr() as *mut mem::MaybeUninit<T>;
+      {
+         let _guard = UnlockGuard::new(self);
+         let slots = unsafe { core::slice::from_raw_parts_mut(dst, src.len()) };
+         let mut init = InitGuard::new(slots);
+         for item in src {
+            init.push(item.clone());
+         }
+         init.finish();
+      }
+
+      self.len = src.len();
+   }
 }
 
 impl<T: Clone + Zeroize> Clone for SecureVec<T> {
    fn clone(&self) -> Self {
       let mut new_vec = SecureVec::new_with_capacity(self.capacity).unwrap();
+      new_vec.fixed_capacity = self.fixed_capacity;
       self.unlock_slice(|src_slice| {
          new_vec.init_from_clone(src_slice);
       });
@@ -605,14 +1038,60 @@ impl<T: Clone + Zeroize> Clone for SecureVec<T> {
    }
 }
 
-impl<const LENGTH: usize> From<SecureArray<u8, LENGTH>> for SecureVec<u8> {
-   fn from(array: SecureArray<u8, LENGTH>) -> Self {
-      let mut new_vec = SecureVec::new_with_capacity(LENGTH)
-         .expect("Failed to allocate SecureVec during conversion");
-      array.unlock(|array_slice| {
-         new_vec.init_from_clone(array_slice);
-      });
-      new_vec
+impl<T: Zeroize, const LENGTH: usize> From<SecureArray<T, LENGTH>> for SecureVec<T> {
+   /// Converts a `SecureArray` into a `SecureVec` by taking over its allocation.
+   ///
+   /// No element is copied or cloned and the memory stays locked.
+   fn from(array: SecureArray<T, LENGTH>) -> Self {
+      let ptr = array.into_raw();
+      unsafe { SecureVec::from_raw_parts(ptr, LENGTH, LENGTH) }
+   }
+}
+
+impl SecureVec<u8> {
+   /// Converts the vector into a `SecureString` by reusing its allocation.
+   ///
+   /// # Errors
+   /// Returns [Error::InvalidUtf8] if the bytes are not valid UTF-8,
+   /// in that case the vector is dropped and zeroized.
+   pub fn into_string(self) -> Result<SecureString, Error> {
+      SecureString::try_from(self)
+   }
+
+   /// Creates a vector of `len` random bytes from the OS random number generator
+   ///
+   /// The bytes are written by `getrandom` straight into the unlocked secure memory,
+   /// they never exist anywhere else.
+   ///
+   /// # Errors
+   /// Returns [Error::RandomFailed] if the OS random number generator fails.
+   #[cfg(feature = "use_os")]
+   pub fn random(len: usize) -> Result<Self, Error> {
+      let mut vec = Self::zeroed(len)?;
+      vec.unlock_slice_mut(getrandom::fill)
+         .map_err(|_| Error::RandomFailed)?;
+      Ok(vec)
+   }
+
+   /// Creates a vector of `len` random bytes from the given cryptographic RNG
+   ///
+   /// The bytes are written straight into the unlocked secure memory.
+   #[cfg(feature = "rand_core")]
+   pub fn random_from_rng<R>(len: usize, rng: &mut R) -> Result<Self, Error>
+   where
+      R: rand_core::CryptoRng + ?Sized,
+   {
+      let mut vec = Self::zeroed(len)?;
+      vec.unlock_slice_mut(|bytes| rng.fill_bytes(bytes));
+      Ok(vec)
+   }
+
+   /// Allocates `len` zero bytes
+   #[cfg(any(feature = "use_os", feature = "rand_core"))]
+   pub(crate) fn zeroed(len: usize) -> Result<Self, Error> {
+      let mut vec = Self::new_with_capacity(len)?;
+      vec.append_from_iter(core::iter::repeat_n(0u8, len));
+      Ok(vec)
    }
 }
 
@@ -692,9 +1171,8 @@ impl<'de> serde::Deserialize<'de> for SecureVec<u8> {
 ///
 /// This struct is created by the `drain` method on `SecureVec`.
 ///
-/// # Safety
-/// The returned `Drain` iterator must not be forgotten (via `mem::forget`).
-/// Forgetting the iterator sets the len of `SecureVec` to 0 and the memory will remain unlocked
+/// The vector's memory is only unlocked for the duration of a single `next()` call
+/// and while the iterator is being dropped, so leaking it never leaves the memory unlocked.
 pub struct Drain<'a, T: Zeroize + 'a> {
    vec_ref: &'a mut SecureVec<T>,
    drain_start_index: usize,
@@ -712,10 +1190,12 @@ impl<'a, T: Zeroize> Iterator for Drain<'a, T> {
 
    fn next(&mut self) -> Option<T> {
       if self.current_drain_iter_index < self.drain_end_index {
-         // SecureVec is already unlocked by the `drain` method.
+         let _guard = UnlockGuard::new(self.vec_ref);
          unsafe {
             let item_ptr = self.vec_ref.ptr.as_ptr().add(self.current_drain_iter_index);
             let item = ptr::read(item_ptr);
+            // The value now lives with the caller, wipe the bytes left behind in the slot.
+            wipe_slots(item_ptr, 1);
             self.current_drain_iter_index += 1;
             Some(item)
          }
@@ -734,76 +1214,106 @@ impl<'a, T: Zeroize> ExactSizeIterator for Drain<'a, T> {}
 
 impl<'a, T: Zeroize> Drop for Drain<'a, T> {
    fn drop(&mut self) {
+      let guard = UnlockGuard::new(self.vec_ref);
+      let base_ptr = self.vec_ref.ptr.as_ptr();
+
       unsafe {
-         // The vec_ref's memory is currently unlocked.
-         if mem::needs_drop::<T>() {
-            let mut current_ptr = self.vec_ref.ptr.as_ptr().add(self.current_drain_iter_index);
-            let end_ptr = self.vec_ref.ptr.as_ptr().add(self.drain_end_index);
-            while current_ptr < end_ptr {
-               ptr::drop_in_place(current_ptr);
-               current_ptr = current_ptr.add(1);
-            }
+         // Zeroize and drop the items that were never yielded.
+         let mut current_ptr = base_ptr.add(self.current_drain_iter_index);
+         let end_ptr = base_ptr.add(self.drain_end_index);
+         while current_ptr < end_ptr {
+            (*current_ptr).zeroize();
+            ptr::drop_in_place(current_ptr);
+            current_ptr = current_ptr.add(1);
          }
 
-         let hole_dst_ptr = self.vec_ref.ptr.as_ptr().add(self.drain_start_index);
-         let tail_src_ptr = self.vec_ref.ptr.as_ptr().add(self.drain_end_index);
+         // Every slot in the drained range is now moved-out or dropped, wipe them all.
+         wipe_slots(
+            base_ptr.add(self.drain_start_index),
+            self.drain_end_index - self.drain_start_index,
+         );
 
+         // Shift the tail into the hole. `ptr::copy` moves the values bitwise,
+         // so the source slots must be wiped rather than zeroized or dropped.
          if self.tail_len > 0 {
-            ptr::copy(tail_src_ptr, hole_dst_ptr, self.tail_len);
+            ptr::copy(
+               base_ptr.add(self.drain_end_index),
+               base_ptr.add(self.drain_start_index),
+               self.tail_len,
+            );
          }
 
-         // The new length of the vector.
          let new_len = self.drain_start_index + self.tail_len;
+         wipe_slots(
+            base_ptr.add(new_len),
+            self.original_vec_len - new_len,
+         );
 
-         // Process the memory region that is no longer part of the active vector's content.
-         // This region is from `vec_ref.ptr + new_len` up to `vec_ref.ptr + original_vec_len`.
-         // It contains:
-         //    a) Original data of the latter part of the drained slice (if not overwritten by tail).
-         //       These were dropped in step 1 if T:Drop.
-         //    b) Original data of the tail items (which have now been copied).
-         //       These need to be dropped if T:Drop, as ptr::copy doesn't drop the source.
-         // After any necessary drops, this entire region must be zeroized.
-
-         let mut current_cleanup_ptr = self.vec_ref.ptr.as_ptr().add(new_len);
-         let end_cleanup_ptr = self.vec_ref.ptr.as_ptr().add(self.original_vec_len);
-
-         // Determine the start of the original tail's memory region
-         let original_tail_start_ptr_val = tail_src_ptr as usize;
-
-         while current_cleanup_ptr < end_cleanup_ptr {
-            if mem::needs_drop::<T>() {
-               let current_ptr_val = current_cleanup_ptr as usize;
-               let original_tail_end_ptr_val =
-                  original_tail_start_ptr_val + self.tail_len * mem::size_of::<T>();
-
-               if current_ptr_val >= original_tail_start_ptr_val
-                  && current_ptr_val < original_tail_end_ptr_val
-               {
-                  // This element was part of the original tail. ptr::copy moved its value.
-                  // The original instance here needs to be dropped.
-                  ptr::drop_in_place(current_cleanup_ptr);
-               }
-               // Else, it was part of the drained range (not covered by tail move).
-               // If it needed dropping, it was handled in step 1.
-            }
+         drop(guard);
+         self.vec_ref.len = new_len;
+      }
+   }
+}
 
-            // Zeroize the memory of this element.
-            (*current_cleanup_ptr).zeroize();
-            current_cleanup_ptr = current_cleanup_ptr.add(1);
-         }
+/// Fills uninitialized slots from the front and keeps track of how many are written.
+///
+/// If a panic unwinds before [`finish`](Self::finish) (eg. from `T::clone` or a `map` closure),
+/// the slots written so far are zeroized and dropped, so values that own heap memory
+/// are neither leaked nor freed unwiped.
+pub(crate) struct InitGuard<'a, T: Zeroize> {
+   slots: &'a mut [mem::MaybeUninit<T>],
+   init: usize,
+}
 
-         // Update the SecureVec's length.
-         self.vec_ref.len = new_len;
+impl<'a, T: Zeroize> InitGuard<'a, T> {
+   pub(crate) fn new(slots: &'a mut [mem::MaybeUninit<T>]) -> Self {
+      InitGuard { slots, init: 0 }
+   }
+
+   /// Writes `value` to the next slot
+   ///
+   /// # Panics
+   /// Panics if every slot is already written.
+   pub(crate) fn push(&mut self, value: T) {
+      self.slots[self.init].write(value);
+      self.init += 1;
+   }
 
-         // Relock the SecureVec's memory.
-         let ok = self.vec_ref.lock_memory();
-         debug_assert!(ok, "Drain::drop: lock_memory failed");
+   /// Hands the written slots over to the caller
+   pub(crate) fn finish(self) {
+      mem::forget(self);
+   }
+}
+
+impl<T: Zeroize> Drop for InitGuard<'_, T> {
+   fn drop(&mut self) {
+      for slot in &mut self.slots[..self.init] {
+         unsafe {
+            let value = slot.assume_init_mut();
+            value.zeroize();
+            ptr::drop_in_place(value);
+         }
       }
    }
 }
 
+/// Overwrites the raw bytes of `count` slots starting at `ptr` with zeros.
+///
+/// Used for slots whose value has been moved out, where calling `T::zeroize`
+/// would touch memory that is now owned by someone else.
+///
+/// # Safety
+/// The memory must be unlocked and valid for writes of `count` elements.
+pub(crate) unsafe fn wipe_slots<T>(ptr: *mut T, count: usize) {
+   let byte_len = count * mem::size_of::<T>();
+   if byte_len > 0 {
+      let bytes = unsafe { core::slice::from_raw_parts_mut(ptr as *mut u8, byte_len) };
+      bytes.zeroize();
+   }
+}
+
 // Helper function to resolve RangeBounds to (start, end) indices
-fn resolve_range_indices<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+pub(crate) fn resolve_range_indices<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
    let start_bound = range.start_bound();
    let end_bound = range.end_bound();
 
@@ -811,27 +1321,27 @@ fn resolve_range_indices<R: RangeBounds<usize>>(range: R, len: usize) -> (usize,
       Bound::Included(&s) => s,
       Bound::Excluded(&s) => s
          .checked_add(1)
-         .unwrap_or_else(|| panic!("attempted to start drain at Excluded(usize::MAX)")),
+         .unwrap_or_else(|| panic!("attempted to start range at Excluded(usize::MAX)")),
       Bound::Unbounded => 0,
    };
 
    let end = match end_bound {
       Bound::Included(&e) => e
          .checked_add(1)
-         .unwrap_or_else(|| panic!("attempted to end drain at Included(usize::MAX)")),
+         .unwrap_or_else(|| panic!("attempted to end range at Included(usize::MAX)")),
       Bound::Excluded(&e) => e,
       Bound::Unbounded => len,
    };
 
    if start > end {
       panic!(
-         "drain range start ({}) must be less than or equal to end ({})",
+         "range start ({}) must be less than or equal to end ({})",
          start, end
       );
    }
    if end > len {
       panic!(
-         "drain range end ({}) out of bounds for slice of length {}",
+         "range end ({}) out of bounds for slice of length {}",
          end, len
       );
    }
@@ -1012,6 +1522,36 @@ mod tests {
       });
    }
 
+   #[test]
+   fn test_from_secure_array_non_copy() {
+      let people = [create_test_person(1), create_test_person(2)];
+      let array = SecureArray::from_slice(&people).unwrap();
+      let mut vec: SecureVec<Person> = array.into();
+      assert_eq!(vec.len(), 2);
+
+      // The vector owns the old allocation and can still grow from it.
+      vec.push(create_test_person(3));
+      vec.unlock_slice(|slice| {
+         assert_eq!(slice[..2], people);
+         assert_eq!(slice[2], create_test_person(3));
+      });
+   }
+
+   #[test]
+   fn test_into_string() {
+      let vec = SecureVec::from_slice("hello".as_bytes()).unwrap();
+      let string = vec.into_string().unwrap();
+      string.unlock_str(|str| {
+         assert_eq!(str, "hello");
+      });
+
+      let invalid = SecureVec::from_slice(&[0xFFu8, 0xFE]).unwrap();
+      assert!(matches!(
+         invalid.into_string(),
+         Err(Error::InvalidUtf8)
+      ));
+   }
+
    #[test]
    fn lock_unlock_works() {
       let secure: SecureVec<u8> = SecureVec::new().unwrap();
@@ -1076,6 +1616,204 @@ mod tests {
       });
    }
 
+   #[test]
+   fn test_forget_drain_keeps_memory_locked() {
+      let arg = "CRASH_TEST_SECUREVEC_FORGET_DRAIN";
+
+      if std::env::args().any(|a| a == arg) {
+         let vec: Vec<u8> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+         let mut secure = SecureVec::from_vec(vec).unwrap();
+         let mut drain = secure.drain(2..6);
+         assert_eq!(drain.next(), Some(3));
+         core::mem::forget(drain);
+         // The pages must be PROT_NONE again, so this read has to fault.
+         let _value = unsafe { core::hint::black_box(*secure.ptr.as_ptr()) };
+
+         std::process::exit(1);
+      }
+
+      let child = Command::new(std::env::current_exe().unwrap())
+         .arg("vec::tests::test_forget_drain_keeps_memory_locked")
+         .arg(arg)
+         .arg("--nocapture")
+         .stdout(Stdio::piped())
+         .stderr(Stdio::piped())
+         .spawn()
+         .expect("Failed to spawn child process");
+
+      let output = child.wait_with_output().expect("Failed to wait on child");
+      let status = output.status;
+
+      assert!(
+         !status.success(),
+         "Process exited successfully with code {:?}, but it should have crashed.",
+         status.code()
+      );
+
+      #[cfg(unix)]
+      {
+         use std::os::unix::process::ExitStatusExt;
+         let signal = status
+            .signal()
+            .expect("Process was not terminated by a signal on Unix.");
+         assert!(
+            signal == libc::SIGSEGV || signal == libc::SIGBUS,
+            "Process terminated with unexpected signal: {}",
+            signal
+         );
+      }
+
+      #[cfg(windows)]
+      {
+         const STATUS_ACCESS_VIOLATION: i32 = 0xC0000005_u32 as i32;
+         assert_eq!(
+            status.code(),
+            Some(STATUS_ACCESS_VIOLATION),
+            "Process exited with unexpected code: {:x?}. Expected STATUS_ACCESS_VIOLATION.",
+            status.code()
+         );
+      }
+   }
+
+   #[test]
+   fn test_drain_partial_and_non_copy() {
+      let mut secure = SecureVec::from_vec(vec![1u8, 2, 3, 4, 5, 6]).unwrap();
+      let mut drain = secure.drain(1..4);
+      assert_eq!(drain.next(), Some(2));
+      drop(drain);
+      secure.unlock_slice(|slice| {
+         assert_eq!(slice, &[1, 5, 6]);
+      });
+
+      let mut people = SecureVec::new().unwrap();
+      for i in 0..5 {
+         people.push(create_test_person(i));
+      }
+      let drained: Vec<Person> = people.drain(1..3).collect();
+      assert_eq!(
+         drained,
+         vec![create_test_person(1), create_test_person(2)]
+      );
+      people.unlock_slice(|slice| {
+         assert_eq!(
+            slice,
+            &[
+               create_test_person(0),
+               create_test_person(3),
+               create_test_person(4)
+            ]
+         );
+      });
+   }
+
+   #[test]
+   fn test_drain_into() {
+      let mut src = SecureVec::from_vec(vec![1u8, 2, 3, 4, 5, 6]).unwrap();
+      let mut dst = SecureVec::from_vec(vec![0u8]).unwrap();
+      src.drain_into(1..4, &mut dst);
+      assert_eq!(src.len(), 3);
+      assert_eq!(dst.len(), 4);
+
+      src.unlock_slice(|slice| {
+         assert_eq!(slice, &[1, 5, 6]);
+      });
+      dst.unlock_slice(|slice| {
+         assert_eq!(slice, &[0, 2, 3, 4]);
+      });
+
+      // The vacated tail of the source must be wiped.
+      src.unlock_memory();
+      let vacated = unsafe { core::slice::from_raw_parts(src.ptr.as_ptr().add(3), 3) };
+      assert_eq!(vacated, &[0, 0, 0]);
+      src.lock_memory();
+
+      let mut people = SecureVec::new().unwrap();
+      for i in 0..4 {
+         people.push(create_test_person(i));
+      }
+      let mut moved = SecureVec::new().unwrap();
+      people.drain_into(2.., &mut moved);
+      people.unlock_slice(|slice| {
+         assert_eq!(
+            slice,
+            &[create_test_person(0), create_test_person(1)]
+         );
+      });
+      moved.unlock_slice(|slice| {
+         assert_eq!(
+            slice,
+            &[create_test_person(2), create_test_person(3)]
+         );
+      });
+   }
+
+   #[test]
+   fn test_copy_range_into() {
+      let src = SecureVec::from_vec(vec![1u16, 2, 3, 4]).unwrap();
+      let mut dst = SecureVec::new().unwrap();
+      src.copy_range_into(..2, &mut dst);
+      src.copy_range_into(3..=3, &mut dst);
+
+      src.unlock_slice(|slice| {
+         assert_eq!(slice, &[1, 2, 3, 4]);
+      });
+      dst.unlock_slice(|slice| {
+         assert_eq!(slice, &[1, 2, 4]);
+      });
+   }
+
+   #[test]
+   fn test_unlock_chunks_exact() {
+      let data: Vec<u8> = (0..10).collect();
+      let secure = SecureVec::from_vec(data).unwrap();
+
+      secure.unlock_chunks_exact::<4, _>(|chunks| {
+         assert_eq!(chunks, &[[0, 1, 2, 3], [4, 5, 6, 7]]);
+      });
+
+      secure.unlock_chunks_exact::<16, _>(|chunks| {
+         assert!(chunks.is_empty());
+      });
+
+      let pairs = secure.unlock_chunks_exact(|chunks: &[[u8; 2]]| chunks.len());
+      assert_eq!(pairs, 5);
+   }
+
+   #[test]
+   fn test_split_into_arrays_and_concat() {
+      let data: Vec<u8> = (0..64).collect();
+      let master = SecureVec::from_vec(data.clone()).unwrap();
+
+      let subkeys = master.split_into_arrays::<32>().unwrap();
+      assert_eq!(subkeys.len(), 2);
+      subkeys[0].unlock(|slice| {
+         assert_eq!(slice, &data[..32]);
+      });
+      subkeys[1].unlock(|slice| {
+         assert_eq!(slice, &data[32..]);
+      });
+
+      let joined = SecureVec::concat(&[&subkeys[1], &subkeys[0]]).unwrap();
+      joined.unlock_slice(|slice| {
+         assert_eq!(&slice[..32], &data[32..]);
+         assert_eq!(&slice[32..], &data[..32]);
+      });
+
+      assert!(matches!(
+         master.split_into_arrays::<30>(),
+         Err(Error::LengthMismatch)
+      ));
+
+      let people = SecureVec::from_vec((0..4).map(create_test_person).collect()).unwrap();
+      let pairs = people.split_into_arrays::<2>().unwrap();
+      pairs[1].unlock(|slice| {
+         assert_eq!(
+            slice,
+            &[create_test_person(2), create_test_person(3)]
+         );
+      });
+   }
+
    #[test]
    fn test_drain() {
       let vec: Vec<u8> = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
@@ -1381,4 +2119,85 @@ mod tests {
          assert_eq!(slice[19], 19000);
       });
    }
+
+   #[test]
+   fn test_random() {
+      let a = SecureVec::random(64).unwrap();
+      let b = SecureVec::random(64).unwrap();
+
+      assert_eq!(a.len(), 64);
+      a.unlock_slice(|bytes| assert!(bytes.iter().any(|&b| b != 0)));
+      assert!(a != b);
+
+      let empty = SecureVec::random(0).unwrap();
+      assert!(empty.is_empty());
+   }
+
+   #[cfg(feature = "rand_core")]
+   #[test]
+   fn test_random_from_rng() {
+      let mut rng = crate::tests::CountingRng(0);
+      let vec = SecureVec::random_from_rng(4, &mut rng).unwrap();
+      vec.unlock_slice(|bytes| assert_eq!(bytes, &[1, 2, 3, 4]));
+   }
+
+   #[test]
+   fn test_fixed_capacity() {
+      let mut vec: SecureVec<u8> = SecureVec::with_fixed_capacity(3).unwrap();
+      assert!(vec.is_fixed_capacity());
+      assert_eq!(vec.capacity(), 3);
+      let ptr = vec.ptr;
+
+      vec.try_push(1).unwrap();
+      vec.push(2);
+      vec.try_reserve(1).unwrap();
+      vec.try_push(3).unwrap();
+
+      assert!(matches!(
+         vec.try_push(4),
+         Err(Error::CapacityExceeded)
+      ));
+      assert!(matches!(
+         vec.try_reserve(1),
+         Err(Error::CapacityExceeded)
+      ));
+      assert_eq!(vec.ptr, ptr);
+      assert_eq!(vec.capacity(), 3);
+      vec.unlock_slice(|slice| {
+         assert_eq!(slice, &[1, 2, 3]);
+      });
+
+      // Making room again is fine
+      let _ = vec.drain(..1);
+      vec.try_push(4).unwrap();
+      assert_eq!(vec.ptr, ptr);
+
+      let clone = vec.clone();
+      assert!(clone.is_fixed_capacity());
+
+      assert!(matches!(
+         SecureVec::<u8>::with_fixed_capacity(0),
+         Err(Error::LengthCannotBeZero)
+      ));
+   }
+
+   #[test]
+   #[should_panic(expected = "fixed capacity of 2 exceeded")]
+   fn test_fixed_capacity_push_panics() {
+      let mut vec: SecureVec<u8> = SecureVec::with_fixed_capacity(2).unwrap();
+      vec.push(1);
+      vec.push(2);
+      vec.push(3);
+   }
+
+   #[test]
+   fn test_try_push_grows() {
+      let mut vec: SecureVec<u8> = SecureVec::new().unwrap();
+      assert!(!vec.is_fixed_capacity());
+      for i in 0..10 {
+         vec.try_push(i).unwrap();
+      }
+      assert_eq!(vec.len(), 10);
+      assert!(vec.capacity() >= 10);
+   }
 }